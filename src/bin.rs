@@ -3,6 +3,7 @@
 //! This module handles command line arguments, collects together all the available stars, and runs
 //! the chosen one using data supplied via standard input.
 
+use aoclib::knot_hash::knot_hash;
 use aoclib::{StarId, all_stars};
 use std::env;
 use std::io::{self, Read};
@@ -13,6 +14,25 @@ fn print_usage() {
     eprintln!(" runs the given star (pass input via stdin)");
     eprintln!("aoc --list");
     eprintln!(" prints the list of available stars");
+    eprintln!("aoc hash knot [<text>]");
+    eprintln!(" prints the knot hash of the given text (empty if omitted)");
+    eprintln!();
+    eprintln!("Set AOC_VERBOSE=1 to print debugging output from some stars to stderr");
+    eprintln!("Set AOC_BEAM_WIDTH=<n> to use a faster but inexact search in some stars");
+}
+
+/// Prints the hash of some text using the given algorithm
+fn run_hash(program: &str, algorithm: &str, text: &str) -> i32 {
+    match algorithm {
+        "knot" => {
+            println!("{}", knot_hash(text));
+            0
+        }
+        _ => {
+            eprintln!("{}: unknown hash algorithm \"{}\"", program, algorithm);
+            1
+        }
+    }
 }
 
 fn main() {
@@ -23,6 +43,15 @@ fn main() {
             print_usage();
             0
         }
+        _ if args[1] == "hash" => match args.get(2) {
+            // Missing text is hashed as the empty string
+            Some(algorithm) => run_hash(&args[0], algorithm, &args[3..].join(" ")),
+            None => {
+                eprintln!("{}: missing hash algorithm", args[0]);
+                print_usage();
+                1
+            }
+        },
         2 => {
             if let Ok(id) = args[1].parse::<StarId>() {
                 if let Ok(index) = stars.binary_search_by(|probe| probe.0.cmp(&id)) {
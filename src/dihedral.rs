@@ -0,0 +1,88 @@
+//! Rotations and reflections of square grids
+
+use nalgebra::{DMatrix, Scalar};
+
+/// One of the 8 symmetries of a square (the dihedral group of order 8)
+///  The transform flips the square horizontally if flip is set, and then
+///  rotates it clockwise by the given number of quarter turns.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Dihedral {
+    pub flip: bool,
+    pub turns: u8,
+}
+
+impl Dihedral {
+    /// Returns an iterator over all 8 transforms
+    ///  The unflipped rotations come first, starting with the identity.
+    pub fn all() -> impl Iterator<Item = Dihedral> {
+        [false, true]
+            .into_iter()
+            .flat_map(|flip| (0..4).map(move |turns| Dihedral { flip, turns }))
+    }
+
+    /// Returns the transform which undoes this one
+    pub fn inverse(self) -> Dihedral {
+        if self.flip {
+            self
+        } else {
+            Dihedral {
+                flip: false,
+                turns: (4 - self.turns) % 4,
+            }
+        }
+    }
+
+    /// Returns where the point (x, y) in a square of the given size ends up
+    ///  y increases downwards, so clockwise rotation takes the top left
+    ///  corner to the top right.
+    pub fn apply(self, (x, y): (usize, usize), size: usize) -> (usize, usize) {
+        let mut point = if self.flip { (size - 1 - x, y) } else { (x, y) };
+        for _ in 0..self.turns {
+            point = (size - 1 - point.1, point.0);
+        }
+        point
+    }
+
+    /// Transforms a square matrix
+    ///  Matrices are indexed by (row, column), so rows are y coordinates.
+    pub fn transform_matrix<T: Scalar>(self, m: &DMatrix<T>) -> DMatrix<T> {
+        assert!(m.is_square());
+        let size = m.nrows();
+        let inverse = self.inverse();
+        DMatrix::from_fn(size, size, |r, c| {
+            let (x, y) = inverse.apply((c, r), size);
+            m[(y, x)].clone()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn transforms() {
+        let m = DMatrix::from_row_slice(2, 2, &[1, 2, 3, 4]);
+        let all: Vec<Vec<i32>> = Dihedral::all()
+            .map(|d| d.transform_matrix(&m).transpose().iter().copied().collect())
+            .collect();
+        assert_eq!(
+            all,
+            [
+                [1, 2, 3, 4],
+                [3, 1, 4, 2],
+                [4, 3, 2, 1],
+                [2, 4, 1, 3],
+                [2, 1, 4, 3],
+                [4, 2, 3, 1],
+                [3, 4, 1, 2],
+                [1, 3, 2, 4],
+            ]
+        );
+
+        for d in Dihedral::all() {
+            let round_trip = d.inverse().transform_matrix(&d.transform_matrix(&m));
+            assert_eq!(round_trip, m);
+        }
+    }
+}
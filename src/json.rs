@@ -0,0 +1,335 @@
+//! Minimal JSON parser
+
+use std::fmt;
+use std::str::FromStr;
+
+/// A JSON value
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    /// Object members, in the order they appear in the input
+    Object(Vec<(String, Value)>),
+}
+
+/// An error encountered while parsing JSON
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// Byte offset of the error in the input
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+/// Recursive descent parser state
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    /// Creates an error at the current position
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, ParseError> {
+        Err(ParseError {
+            position: self.pos,
+            message: message.into(),
+        })
+    }
+
+    /// Returns the next byte without consuming it
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    /// Skips over any whitespace
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    /// Consumes the given byte (after whitespace) or returns an error
+    fn expect(&mut self, expected: u8) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            self.error(format!("expected '{}'", char::from(expected)))
+        }
+    }
+
+    /// Parses any value
+    fn value(&mut self) -> Result<Value, ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => Ok(Value::String(self.string()?)),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(b't') => self.literal("true", Value::Bool(true)),
+            Some(b'f') => self.literal("false", Value::Bool(false)),
+            Some(b'n') => self.literal("null", Value::Null),
+            Some(_) => self.error("unexpected character"),
+            None => self.error("unexpected end of input"),
+        }
+    }
+
+    /// Parses a literal keyword
+    fn literal(&mut self, word: &str, value: Value) -> Result<Value, ParseError> {
+        if self.input[self.pos..].starts_with(word.as_bytes()) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            self.error("invalid literal")
+        }
+    }
+
+    /// Parses a comma separated list of items between two delimiters
+    fn list<T>(
+        &mut self,
+        open: u8,
+        close: u8,
+        mut item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        self.expect(open)?;
+        let mut items = Vec::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(close) {
+            self.pos += 1;
+            return Ok(items);
+        }
+
+        loop {
+            items.push(item(self)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b) if b == close => {
+                    self.pos += 1;
+                    return Ok(items);
+                }
+                _ => return self.error(format!("expected ',' or '{}'", char::from(close))),
+            }
+        }
+    }
+
+    /// Parses an array
+    fn array(&mut self) -> Result<Value, ParseError> {
+        self.list(b'[', b']', Self::value).map(Value::Array)
+    }
+
+    /// Parses an object
+    fn object(&mut self) -> Result<Value, ParseError> {
+        self.list(b'{', b'}', |p| {
+            p.skip_whitespace();
+            let key = p.string()?;
+            p.expect(b':')?;
+            Ok((key, p.value()?))
+        })
+        .map(Value::Object)
+    }
+
+    /// Parses a string
+    fn string(&mut self) -> Result<String, ParseError> {
+        if self.peek() != Some(b'"') {
+            return self.error("expected string");
+        }
+        self.pos += 1;
+
+        let mut result = String::new();
+        loop {
+            let start = self.pos;
+            while self
+                .peek()
+                .is_some_and(|b| b != b'"' && b != b'\\' && b >= 0x20)
+            {
+                self.pos += 1;
+            }
+
+            // The input came from a str and we only stop on ASCII bytes, so this is valid UTF-8
+            result.push_str(std::str::from_utf8(&self.input[start..self.pos]).unwrap());
+
+            match self.peek() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(result);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    result.push(self.escape()?);
+                }
+                Some(_) => return self.error("control character in string"),
+                None => return self.error("unterminated string"),
+            }
+        }
+    }
+
+    /// Parses the character after a backslash in a string
+    fn escape(&mut self) -> Result<char, ParseError> {
+        let c = match self.peek() {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'/') => '/',
+            Some(b'b') => '\u{8}',
+            Some(b'f') => '\u{c}',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') => {
+                self.pos += 1;
+                let high = self.hex4()?;
+                let code = if (0xD800..0xDC00).contains(&high) {
+                    // Surrogate pair
+                    if !self.input[self.pos..].starts_with(b"\\u") {
+                        return self.error("unpaired surrogate");
+                    }
+                    self.pos += 2;
+                    let low = self.hex4()?;
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return self.error("invalid low surrogate");
+                    }
+                    0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                } else {
+                    high
+                };
+
+                return match char::from_u32(code) {
+                    Some(c) => Ok(c),
+                    None => self.error("invalid unicode escape"),
+                };
+            }
+            _ => return self.error("invalid escape"),
+        };
+
+        self.pos += 1;
+        Ok(c)
+    }
+
+    /// Parses 4 hex digits
+    fn hex4(&mut self) -> Result<u32, ParseError> {
+        let digits = self
+            .input
+            .get(self.pos..self.pos + 4)
+            .filter(|d| d.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|d| std::str::from_utf8(d).ok())
+            .and_then(|d| u32::from_str_radix(d, 16).ok());
+
+        match digits {
+            Some(value) => {
+                self.pos += 4;
+                Ok(value)
+            }
+            None => self.error("expected 4 hex digits"),
+        }
+    }
+
+    /// Parses a number
+    fn number(&mut self) -> Result<Value, ParseError> {
+        let start = self.pos;
+        let skip_digits = |p: &mut Self| {
+            let digits_start = p.pos;
+            while p.peek().is_some_and(|b| b.is_ascii_digit()) {
+                p.pos += 1;
+            }
+            p.pos > digits_start
+        };
+
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        if !skip_digits(self) {
+            return self.error("expected digits");
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if !skip_digits(self) {
+                return self.error("expected digits after decimal point");
+            }
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if !skip_digits(self) {
+                return self.error("expected exponent digits");
+            }
+        }
+
+        // Only ASCII number characters were consumed above
+        let text = std::str::from_utf8(&self.input[start..self.pos]).unwrap();
+        Ok(Value::Number(text.parse().unwrap()))
+    }
+}
+
+impl FromStr for Value {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Value, ParseError> {
+        let mut parser = Parser {
+            input: s.as_bytes(),
+            pos: 0,
+        };
+
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos != s.len() {
+            return parser.error("trailing characters");
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn values() {
+        assert_eq!(
+            r#" {"a": [1, -2.5e1, true, false, null], "b": {}} "#.parse(),
+            Ok(Value::Object(vec![
+                (
+                    "a".to_string(),
+                    Value::Array(vec![
+                        Value::Number(1.0),
+                        Value::Number(-25.0),
+                        Value::Bool(true),
+                        Value::Bool(false),
+                        Value::Null,
+                    ])
+                ),
+                ("b".to_string(), Value::Object(Vec::new())),
+            ]))
+        );
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(
+            r#""a\"\\\/\n\u00e9\ud83d\ude00""#.parse(),
+            Ok(Value::String("a\"\\/\n\u{e9}\u{1f600}".to_string()))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let position = |s: &str| s.parse::<Value>().unwrap_err().position;
+        assert_eq!(position("[1, 2"), 5);
+        assert_eq!(position("[1 2]"), 3);
+        assert_eq!(position(r#"{"a" 1}"#), 5);
+        assert_eq!(position(r#""\q""#), 2);
+        assert_eq!(position("[1] x"), 4);
+        assert_eq!(position("-"), 1);
+    }
+}
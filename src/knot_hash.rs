@@ -0,0 +1,231 @@
+//! Knot hash (from 2017 days 10 and 14)
+//!
+//! The hash ties knots in a circular list of numbers by reversing sections
+//! of it, using each input byte as the length of a section. After several
+//! rounds, the list is condensed by XORing together each block of 16
+//! numbers.
+//!
+//! ```
+//! use aoclib::knot_hash::{KnotHasher, knot_hash};
+//!
+//! assert_eq!(knot_hash("AoC 2017").to_hex(), "33efeb34ea91902bb2f59c9920caa6cd");
+//!
+//! let mut hasher = KnotHasher::new();
+//! hasher.update("AoC ");
+//! hasher.update(b"2017");
+//! assert_eq!(hasher.finish(), knot_hash("AoC 2017"));
+//! ```
+
+use std::fmt;
+use std::ops::BitXor;
+
+/// Lengths appended to the input before hashing
+const LENGTHS_SUFFIX: [u8; 5] = [17, 31, 73, 47, 23];
+
+/// Number of list elements combined into each byte of the hash
+const BLOCK_SIZE: usize = 16;
+
+/// A knot hasher which can be fed data incrementally
+///  Every round uses the whole input, so the input is buffered until the
+///  hash is calculated.
+#[derive(Clone, Debug)]
+pub struct KnotHasher {
+    list_size: usize,
+    rounds: usize,
+    suffix: bool,
+    lengths: Vec<u8>,
+}
+
+impl KnotHasher {
+    /// Creates a hasher using the standard 256 element list and 64 rounds
+    pub fn new() -> KnotHasher {
+        KnotHasher {
+            list_size: 256,
+            rounds: 64,
+            suffix: true,
+            lengths: Vec::new(),
+        }
+    }
+
+    /// Creates a hasher which runs one round on a list of the given size
+    /// without appending the standard suffix to the lengths
+    ///  This is the simplified hash from the first part of 2017 day 10.
+    pub fn single_round(list_size: usize) -> KnotHasher {
+        KnotHasher {
+            list_size,
+            rounds: 1,
+            suffix: false,
+            lengths: Vec::new(),
+        }
+    }
+
+    /// Sets the number of elements in the list
+    pub fn list_size(mut self, list_size: usize) -> KnotHasher {
+        self.list_size = list_size;
+        self
+    }
+
+    /// Sets the number of rounds to run
+    pub fn rounds(mut self, rounds: usize) -> KnotHasher {
+        self.rounds = rounds;
+        self
+    }
+
+    /// Adds more data to the hash
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.lengths.extend_from_slice(data.as_ref());
+    }
+
+    /// Runs all the rounds and returns the resulting list (the sparse hash)
+    ///  Panics if any length is larger than the list.
+    pub fn sparse(&self) -> Vec<usize> {
+        let suffix: &[u8] = if self.suffix { &LENGTHS_SUFFIX } else { &[] };
+        let lengths: Vec<usize> = self
+            .lengths
+            .iter()
+            .chain(suffix)
+            .map(|&l| usize::from(l))
+            .collect();
+
+        let size = self.list_size;
+        let mut list: Vec<usize> = (0..size).collect();
+        let mut pos = 0;
+        let mut skip = 0;
+        for _ in 0..self.rounds {
+            for &length in &lengths {
+                assert!(length <= size, "length {length} is larger than the list");
+                for i in 0..length / 2 {
+                    list.swap((pos + i) % size, (pos + length - 1 - i) % size);
+                }
+
+                pos = (pos + length + skip) % size;
+                skip += 1;
+            }
+        }
+
+        list
+    }
+
+    /// Calculates the hash by XORing blocks of the sparse hash together
+    ///  The list size must be a multiple of 16 and at most 256.
+    pub fn finish(&self) -> KnotHash {
+        assert!(
+            self.list_size <= 256 && self.list_size.is_multiple_of(BLOCK_SIZE),
+            "list size must be a multiple of {BLOCK_SIZE} up to 256"
+        );
+
+        let sparse = self.sparse();
+        KnotHash(
+            sparse
+                .chunks(BLOCK_SIZE)
+                .map(|block| block.iter().map(|&v| v as u8).fold(0, u8::bitxor))
+                .collect(),
+        )
+    }
+}
+
+impl Default for KnotHasher {
+    fn default() -> KnotHasher {
+        KnotHasher::new()
+    }
+}
+
+/// The result of a knot hash
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct KnotHash(Vec<u8>);
+
+impl KnotHash {
+    /// Returns the bytes of the hash
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the hash as a lowercase hex string
+    pub fn to_hex(&self) -> String {
+        self.to_string()
+    }
+
+    /// Returns an iterator over the bits of the hash
+    ///  Bits are returned from the most significant bit of the first byte.
+    pub fn bits(&self) -> impl Iterator<Item = bool> + '_ {
+        self.0
+            .iter()
+            .flat_map(|&b| (0..8).rev().map(move |i| b & (1 << i) != 0))
+    }
+}
+
+/// Displays the hash in hex
+impl fmt::Display for KnotHash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for b in &self.0 {
+            write!(f, "{b:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Calculates the standard knot hash of some data
+pub fn knot_hash(data: impl AsRef<[u8]>) -> KnotHash {
+    let mut hasher = KnotHasher::new();
+    hasher.update(data);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn published_examples() {
+        let mut hasher = KnotHasher::single_round(5);
+        hasher.update([3, 4, 1, 5]);
+        assert_eq!(hasher.sparse(), [3, 4, 2, 1, 0]);
+
+        assert_eq!(knot_hash("").to_hex(), "a2582a3a0e66e6e86e3812dcb672a272");
+        assert_eq!(
+            knot_hash("AoC 2017").to_hex(),
+            "33efeb34ea91902bb2f59c9920caa6cd"
+        );
+        assert_eq!(
+            knot_hash("1,2,3").to_hex(),
+            "3efbe78a8d82f29979031a4aa0b16a9d"
+        );
+        assert_eq!(
+            knot_hash("1,2,4").to_hex(),
+            "63960835bcdc130f0b66d7ff4f6a5a8e"
+        );
+    }
+
+    #[test]
+    fn incremental() {
+        let mut hasher = KnotHasher::new();
+        hasher.update("1,");
+        hasher.update(String::from("2,"));
+        hasher.update(b"3");
+        assert_eq!(hasher.finish(), knot_hash("1,2,3"));
+    }
+
+    #[test]
+    fn bits() {
+        // First row of the 2017 day 14 example starts with ##.#.#..
+        let hash = knot_hash("flqrgnkx-0");
+        let row: String = hash
+            .bits()
+            .take(8)
+            .map(|b| if b { '#' } else { '.' })
+            .collect();
+        assert_eq!(row, "##.#.#..");
+        assert_eq!(hash.bits().count(), 128);
+    }
+
+    #[test]
+    fn parameters() {
+        let mut hasher = KnotHasher::new().list_size(128).rounds(8);
+        hasher.update("1,2,3");
+        assert_eq!(hasher.finish().as_bytes().len(), 8);
+
+        let mut hasher = KnotHasher::new().list_size(256).rounds(64);
+        hasher.update("1,2,3");
+        assert_eq!(hasher.finish(), knot_hash("1,2,3"));
+    }
+}
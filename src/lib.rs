@@ -6,9 +6,16 @@
 use std::fmt;
 use std::str::FromStr;
 
+mod dihedral;
 mod direction;
+mod json;
+pub mod knot_hash;
 #[macro_use]
 mod macros;
+mod md5;
+mod options;
+mod search;
+pub mod snailfish;
 mod vector;
 
 mod yr2015;
@@ -18,6 +25,7 @@ mod yr2020;
 mod yr2021;
 mod yr2022;
 mod yr2023;
+mod yr2024;
 mod yr2025;
 
 /// Function type for all star functions
@@ -63,6 +71,7 @@ pub fn all_stars() -> Vec<(StarId, StarFunction)> {
     result.append(&mut yr2021::stars());
     result.append(&mut yr2022::stars());
     result.append(&mut yr2023::stars());
+    result.append(&mut yr2024::stars());
     result.append(&mut yr2025::stars());
     result
 }
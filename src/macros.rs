@@ -11,14 +11,24 @@ macro_rules! vec_many_days
     };
 }
 
-/// Generates a vector containing 24 stars (day 1 to 12)
+/// Generates a vector containing all 23 stars (minus 12-2) for a 12 day
+/// year. Designed to be returned from a "stars" function.
 macro_rules! vec_12_days {
-    ($year:expr) => {
-        vec_many_days!(
+    ($year:expr) => {{
+        let mut result: Vec<(StarId, StarFunction)> = vec_many_days!(
             $year, 1, day1, 2, day2, 3, day3, 4, day4, 5, day5, 6, day6, 7, day7, 8, day8, 9, day9,
-            10, day10, 11, day11, 12, day12
-        )
-    };
+            10, day10, 11, day11
+        );
+        result.push((
+            StarId {
+                year: $year,
+                day: 12,
+                star: 1,
+            },
+            day12::star1,
+        ));
+        result
+    }};
 }
 
 /// Generates a vector containing 48 stars (day 1 to 24)
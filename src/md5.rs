@@ -0,0 +1,214 @@
+//! MD5 message digest (RFC 1321) and parallel hash mining
+
+use std::io::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+
+/// Per-round left rotation amounts
+const SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9,
+    14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15,
+    21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+/// Per-round additive constants (floor(abs(sin(i + 1)) * 2^32))
+const CONSTANTS: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+/// Number of suffixes each thread claims at once when mining
+const MINING_CHUNK: u64 = 4096;
+
+/// An MD5 hasher which can be fed data incrementally
+#[derive(Clone, Debug)]
+pub struct Md5 {
+    state: [u32; 4],
+    buffer: [u8; 64],
+    length: u64,
+}
+
+impl Md5 {
+    /// Creates a new hasher
+    pub fn new() -> Md5 {
+        Md5 {
+            state: [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476],
+            buffer: [0; 64],
+            length: 0,
+        }
+    }
+
+    /// Processes a single 64 byte block
+    fn process_block(&mut self, block: &[u8; 64]) {
+        let words: [u32; 16] = std::array::from_fn(|i| {
+            u32::from_le_bytes(block[i * 4..i * 4 + 4].try_into().unwrap())
+        });
+
+        let [mut a, mut b, mut c, mut d] = self.state;
+        for i in 0..64 {
+            let (f, g) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                2 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16),
+            };
+
+            let rotated = a
+                .wrapping_add(f)
+                .wrapping_add(CONSTANTS[i])
+                .wrapping_add(words[g])
+                .rotate_left(SHIFTS[i]);
+            (a, b, c, d) = (d, b.wrapping_add(rotated), b, c);
+        }
+
+        for (state, value) in self.state.iter_mut().zip([a, b, c, d]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+
+    /// Adds more data to the hash
+    pub fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let offset = (self.length % 64) as usize;
+            let count = data.len().min(64 - offset);
+            self.buffer[offset..offset + count].copy_from_slice(&data[..count]);
+            self.length += count as u64;
+            data = &data[count..];
+
+            if offset + count == 64 {
+                let block = self.buffer;
+                self.process_block(&block);
+            }
+        }
+    }
+
+    /// Pads the message and returns the final digest
+    pub fn finish(mut self) -> [u8; 16] {
+        let bit_length = self.length.wrapping_mul(8);
+        self.update(&[0x80]);
+        while self.length % 64 != 56 {
+            self.update(&[0]);
+        }
+        self.update(&bit_length.to_le_bytes());
+
+        let mut digest = [0; 16];
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        digest
+    }
+}
+
+impl Default for Md5 {
+    fn default() -> Md5 {
+        Md5::new()
+    }
+}
+
+/// Calculates the MD5 digest of some data
+pub fn md5(data: impl AsRef<[u8]>) -> [u8; 16] {
+    let mut hasher = Md5::new();
+    hasher.update(data.as_ref());
+    hasher.finish()
+}
+
+/// Returns the number of leading zero hex digits in a digest
+pub fn leading_zero_nibbles(digest: &[u8; 16]) -> usize {
+    let zero_bits: u32 = digest
+        .iter()
+        .scan(true, |all_zero, &b| {
+            let bits = if *all_zero { b.leading_zeros() } else { 0 };
+            *all_zero &= b == 0;
+            Some(bits)
+        })
+        .sum();
+    (zero_bits / 4) as usize
+}
+
+/// Finds the smallest suffix >= start where the MD5 digest of the prefix
+/// followed by the suffix in decimal satisfies the predicate
+///  The search is split across all available threads.
+pub fn find_suffix<P>(prefix: &[u8], start: u64, predicate: P) -> u64
+where
+    P: Fn(&[u8; 16]) -> bool + Sync,
+{
+    let next_chunk = AtomicU64::new(start);
+    let best = AtomicU64::new(u64::MAX);
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                let mut message = prefix.to_vec();
+                loop {
+                    let chunk = next_chunk.fetch_add(MINING_CHUNK, Ordering::Relaxed);
+                    if chunk >= best.load(Ordering::Relaxed) {
+                        break;
+                    }
+
+                    for suffix in chunk..chunk + MINING_CHUNK {
+                        message.truncate(prefix.len());
+                        write!(message, "{suffix}").unwrap();
+                        if predicate(&md5(&message)) {
+                            best.fetch_min(suffix, Ordering::Relaxed);
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    best.into_inner()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use itertools::Itertools;
+
+    fn hex(data: &str) -> String {
+        md5(data).iter().map(|b| format!("{b:02x}")).join("")
+    }
+
+    #[test]
+    fn rfc1321_vectors() {
+        assert_eq!(hex(""), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(hex("a"), "0cc175b9c0f1b6a831c399e269772661");
+        assert_eq!(hex("abc"), "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(hex("message digest"), "f96b697d7cb7938d525a2f31aaf161d0");
+        assert_eq!(
+            hex("abcdefghijklmnopqrstuvwxyz"),
+            "c3fcd3d76192e4007dfb496cca67e13b"
+        );
+        assert_eq!(
+            hex("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789"),
+            "d174ab98d277d9f5a5611c2c9f419d9f"
+        );
+        assert_eq!(
+            hex("12345678901234567890123456789012345678901234567890123456789012345678901234567890"),
+            "57edf4a22be3c955ac49da2e2107b67a"
+        );
+    }
+
+    #[test]
+    fn incremental() {
+        let mut hasher = Md5::new();
+        hasher.update(b"message ");
+        hasher.update(b"digest");
+        assert_eq!(hasher.finish(), md5("message digest"));
+    }
+
+    #[test]
+    fn zero_nibbles() {
+        assert_eq!(leading_zero_nibbles(&md5("abcdef609043")), 5);
+        assert_eq!(leading_zero_nibbles(&[0xff; 16]), 0);
+        assert_eq!(leading_zero_nibbles(&[0; 16]), 32);
+    }
+}
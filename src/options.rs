@@ -0,0 +1,25 @@
+//! Runtime options for debugging and tuning stars
+//!
+//! Options are read from environment variables so they work with any star
+//! without changing its input.
+
+use std::env;
+
+/// Returns true if stars should print extra debugging output to stderr
+///  This is enabled by setting AOC_VERBOSE to anything other than an empty
+///  string or 0.
+pub fn verbose() -> bool {
+    env::var_os("AOC_VERBOSE").is_some_and(|v| !v.is_empty() && v != "0")
+}
+
+/// Returns the beam width to use for searches which support beam search
+///  This is set with AOC_BEAM_WIDTH (unless it is empty). Beam search is
+///  faster, but may not find the best solution.
+pub fn beam_width() -> Option<usize> {
+    let width = env::var("AOC_BEAM_WIDTH").ok().filter(|w| !w.is_empty())?;
+    Some(
+        width
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid AOC_BEAM_WIDTH {width}")),
+    )
+}
@@ -0,0 +1,222 @@
+//! Branch and bound search for maximisation problems
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A problem which can be solved by branch and bound
+///  The score of a state is the sum of the gains along the path taken to
+///  reach it. The search finds the maximum score of any reachable state.
+pub trait Problem {
+    type State: Clone + Eq + Hash;
+
+    /// Returns the successors of a state along with the score gained by moving to each
+    fn successors(&self, state: &Self::State) -> Vec<(Self::State, u32)>;
+}
+
+/// Function returning an upper bound on the extra score from a state
+type BoundFn<'a, S> = Box<dyn Fn(&S) -> u32 + 'a>;
+
+/// Branch and bound search engine
+///  By default this searches exhaustively. Pruning is enabled by providing
+///  an upper bound function, and repeated states can be skipped by enabling
+///  memoisation. A beam width turns this into a (possibly inexact) beam
+///  search which only keeps the most promising states at each depth.
+pub struct BranchAndBound<'a, P: Problem> {
+    problem: &'a P,
+    bound: Option<BoundFn<'a, P::State>>,
+    memoise: bool,
+    beam_width: Option<usize>,
+}
+
+/// Mutable state used during a search
+struct Search<'v, S> {
+    best: u32,
+    memo: HashMap<S, u32>,
+    visit: &'v mut dyn FnMut(&S, u32),
+}
+
+impl<'a, P: Problem> BranchAndBound<'a, P> {
+    /// Creates a new exhaustive search over a problem
+    pub fn new(problem: &'a P) -> Self {
+        BranchAndBound {
+            problem,
+            bound: None,
+            memoise: false,
+            beam_width: None,
+        }
+    }
+
+    /// Sets the upper bound function
+    ///  This must return an upper bound on the additional score which can be
+    ///  gained from a state. States which cannot beat the best score so far
+    ///  are not expanded.
+    pub fn bound(mut self, bound: impl Fn(&P::State) -> u32 + 'a) -> Self {
+        self.bound = Some(Box::new(bound));
+        self
+    }
+
+    /// Skips states which have already been reached with at least the same score
+    pub fn memoise(mut self) -> Self {
+        self.memoise = true;
+        self
+    }
+
+    /// Only keeps the given number of states with the highest bounds at each depth
+    pub fn beam_width(mut self, width: usize) -> Self {
+        self.beam_width = Some(width);
+        self
+    }
+
+    /// Returns the upper bound on the total score of a state
+    fn total_bound(&self, state: &P::State, score: u32) -> u32 {
+        self.bound
+            .as_ref()
+            .map_or(u32::MAX, |bound| score.saturating_add(bound(state)))
+    }
+
+    /// Visits a state and returns its successors if it should be expanded
+    fn expand(
+        &self,
+        search: &mut Search<P::State>,
+        state: &P::State,
+        score: u32,
+    ) -> Option<Vec<(P::State, u32)>> {
+        if self.memoise {
+            if search.memo.get(state).is_some_and(|&s| s >= score) {
+                return None;
+            }
+            search.memo.insert(state.clone(), score);
+        }
+
+        (search.visit)(state, score);
+        search.best = search.best.max(score);
+        if self.total_bound(state, score) <= search.best {
+            return None;
+        }
+
+        let successors = self.problem.successors(state);
+        Some(
+            successors
+                .into_iter()
+                .map(|(next, gain)| (next, score + gain))
+                .collect(),
+        )
+    }
+
+    /// Searches for the best score reachable from the initial state
+    pub fn solve(&self, initial: P::State) -> u32 {
+        self.explore(initial, |_, _| {})
+    }
+
+    /// Searches from the initial state, calling visit on every state
+    /// expanded along with its score
+    ///  Returns the best score found.
+    pub fn explore(&self, initial: P::State, mut visit: impl FnMut(&P::State, u32)) -> u32 {
+        let mut search = Search {
+            best: 0,
+            memo: HashMap::new(),
+            visit: &mut visit,
+        };
+
+        if let Some(width) = self.beam_width {
+            let mut layer = vec![(initial, 0)];
+            while !layer.is_empty() {
+                let mut next_layer: Vec<_> = layer
+                    .iter()
+                    .filter_map(|(state, score)| self.expand(&mut search, state, *score))
+                    .flatten()
+                    .collect();
+
+                if next_layer.len() > width {
+                    next_layer.sort_by_cached_key(|(state, score)| {
+                        std::cmp::Reverse(self.total_bound(state, *score))
+                    });
+                    next_layer.truncate(width);
+                }
+                layer = next_layer;
+            }
+        } else {
+            let mut stack = vec![(initial, 0)];
+            while let Some((state, score)) = stack.pop() {
+                if let Some(successors) = self.expand(&mut search, &state, score) {
+                    stack.extend(successors);
+                }
+            }
+        }
+
+        search.best
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// 0-1 knapsack where the state is (next item, remaining capacity)
+    struct Knapsack {
+        items: Vec<(u32, u32)>,
+    }
+
+    impl Problem for Knapsack {
+        type State = (usize, u32);
+
+        fn successors(&self, &(i, capacity): &(usize, u32)) -> Vec<((usize, u32), u32)> {
+            let Some(&(weight, value)) = self.items.get(i) else {
+                return Vec::new();
+            };
+
+            let mut result = vec![((i + 1, capacity), 0)];
+            if weight <= capacity {
+                result.push(((i + 1, capacity - weight), value));
+            }
+            result
+        }
+    }
+
+    fn knapsack() -> Knapsack {
+        Knapsack {
+            items: vec![(5, 10), (4, 40), (6, 30), (3, 50), (2, 15), (7, 45), (1, 5)],
+        }
+    }
+
+    #[test]
+    fn exact() {
+        let problem = knapsack();
+        let remaining = |&(i, _): &(usize, u32)| problem.items[i..].iter().map(|p| p.1).sum();
+
+        assert_eq!(BranchAndBound::new(&problem).solve((0, 10)), 110);
+        assert_eq!(
+            BranchAndBound::new(&problem)
+                .bound(remaining)
+                .memoise()
+                .solve((0, 10)),
+            110
+        );
+    }
+
+    #[test]
+    fn explore_visits_states() {
+        let problem = knapsack();
+        let mut states = 0;
+        BranchAndBound::new(&problem).explore((0, 0), |_, score| {
+            assert_eq!(score, 0);
+            states += 1;
+        });
+        assert_eq!(states, 8);
+    }
+
+    #[test]
+    fn beam() {
+        let problem = knapsack();
+        let remaining = |&(i, _): &(usize, u32)| problem.items[i..].iter().map(|p| p.1).sum();
+        let search = |width| {
+            BranchAndBound::new(&problem)
+                .bound(remaining)
+                .beam_width(width)
+                .solve((0, 10))
+        };
+
+        assert!(search(1) <= 110);
+        assert_eq!(search(1000), 110);
+    }
+}
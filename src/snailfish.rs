@@ -0,0 +1,371 @@
+//! Snailfish numbers (from 2021 day 18)
+//!
+//! A snailfish number is a pair whose elements are either regular numbers
+//! or other pairs. Adding two numbers pairs them up and then reduces the
+//! result by exploding pairs nested inside four others and splitting
+//! regular numbers of 10 or more.
+//!
+//! ```
+//! use aoclib::snailfish::Snailfish;
+//!
+//! let a: Snailfish = "[[[[4,3],4],4],[7,[[8,4],9]]]".parse().unwrap();
+//! let b: Snailfish = "[1,1]".parse().unwrap();
+//! let sum = a + b;
+//! assert_eq!(sum.to_string(), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+//! assert_eq!(sum.magnitude(), 1384);
+//!
+//! let error = "[1,10]".parse::<Snailfish>().unwrap_err();
+//! assert_eq!(error.position, 3);
+//! ```
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::Add;
+use std::str::FromStr;
+
+/// Maximum number of leaves in a reduced sum of two reduced numbers
+const MAX_LEAVES: usize = 32;
+
+/// A snailfish number
+///  This is stored as the list of regular numbers from left to right, each
+///  with the number of pairs it is nested inside. Reduction only shuffles
+///  values within the list, so it never needs to allocate. Regular numbers
+///  are parsed as single digits, which keeps every value small enough for a
+///  u8 while adding.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Snailfish {
+    /// (depth, value) for each regular number
+    leaves: Vec<(u8, u8)>,
+}
+
+/// An error encountered while parsing a snailfish number
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// Byte offset of the error in the input
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl Snailfish {
+    /// Explodes the leftmost pair nested inside four pairs
+    ///  Returns false if there are no pairs to explode.
+    fn explode(&mut self) -> bool {
+        let Some(i) = self.leaves.iter().position(|&(depth, _)| depth > 4) else {
+            return false;
+        };
+
+        let (depth, left) = self.leaves[i];
+        let (_, right) = self.leaves[i + 1];
+        if i > 0 {
+            self.leaves[i - 1].1 += left;
+        }
+        if let Some(next) = self.leaves.get_mut(i + 2) {
+            next.1 += right;
+        }
+
+        self.leaves[i] = (depth - 1, 0);
+        self.leaves.remove(i + 1);
+        true
+    }
+
+    /// Splits the leftmost regular number which is 10 or more
+    ///  Returns false if there are no numbers to split.
+    fn split(&mut self) -> bool {
+        let Some(i) = self.leaves.iter().position(|&(_, value)| value >= 10) else {
+            return false;
+        };
+
+        let (depth, value) = self.leaves[i];
+        self.leaves[i] = (depth + 1, value / 2);
+        self.leaves.insert(i + 1, (depth + 1, value.div_ceil(2)));
+        true
+    }
+
+    /// Reduces this number as much as possible
+    fn reduce(&mut self) {
+        while self.explode() || self.split() {}
+    }
+
+    /// Returns the magnitude of this number
+    pub fn magnitude(&self) -> u32 {
+        // Combine pairs of leaves at the same depth as soon as they are complete
+        let mut stack: Vec<(u8, u32)> = Vec::with_capacity(self.leaves.len());
+        for &(depth, value) in &self.leaves {
+            let (mut depth, mut value) = (depth, value.into());
+            while let Some(&(top_depth, left)) = stack.last()
+                && top_depth == depth
+            {
+                stack.pop();
+                (depth, value) = (depth - 1, 3 * left + 2 * value);
+            }
+            stack.push((depth, value));
+        }
+
+        stack[0].1
+    }
+
+    /// Writes the leaves starting at index which form a node at the given depth
+    fn write_node(&self, f: &mut fmt::Formatter, index: &mut usize, depth: u8) -> fmt::Result {
+        let (leaf_depth, value) = self.leaves[*index];
+        if leaf_depth == depth {
+            *index += 1;
+            write!(f, "{value}")
+        } else {
+            write!(f, "[")?;
+            self.write_node(f, index, depth + 1)?;
+            write!(f, ",")?;
+            self.write_node(f, index, depth + 1)?;
+            write!(f, "]")
+        }
+    }
+}
+
+impl Add for &Snailfish {
+    type Output = Snailfish;
+
+    fn add(self, other: &Snailfish) -> Snailfish {
+        let mut leaves = Vec::with_capacity(MAX_LEAVES.max(self.leaves.len() + other.leaves.len()));
+        leaves.extend(
+            self.leaves
+                .iter()
+                .chain(&other.leaves)
+                .map(|&(depth, value)| (depth + 1, value)),
+        );
+
+        let mut result = Snailfish { leaves };
+        result.reduce();
+        result
+    }
+}
+
+impl Add for Snailfish {
+    type Output = Snailfish;
+
+    fn add(self, other: Snailfish) -> Snailfish {
+        &self + &other
+    }
+}
+
+impl Sum for Snailfish {
+    /// Adds a list of numbers in order
+    ///  Panics if the list is empty since there is no zero snailfish number.
+    fn sum<I: Iterator<Item = Snailfish>>(iter: I) -> Snailfish {
+        iter.reduce(|a, b| a + b)
+            .expect("cannot sum an empty list of snailfish numbers")
+    }
+}
+
+impl fmt::Display for Snailfish {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_node(f, &mut 0, 0)
+    }
+}
+
+impl FromStr for Snailfish {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Snailfish, ParseError> {
+        let error = |position, message: &str| {
+            Err(ParseError {
+                position,
+                message: message.to_string(),
+            })
+        };
+
+        // For each open pair, whether its comma has been seen
+        let mut pairs: Vec<bool> = Vec::new();
+        let mut leaves = Vec::new();
+        let mut expect_value = true;
+
+        let mut chars = s.char_indices().peekable();
+        while let Some((pos, c)) = chars.next() {
+            match c {
+                _ if c.is_ascii_whitespace() => {}
+                '[' if expect_value => pairs.push(false),
+                '0'..='9' if expect_value => {
+                    if chars.peek().is_some_and(|(_, d)| d.is_ascii_digit()) {
+                        return error(pos, "regular numbers must be less than 10");
+                    }
+                    leaves.push((pairs.len() as u8, c.to_digit(10).unwrap() as u8));
+                    expect_value = false;
+                }
+                ',' if !expect_value && pairs.last() == Some(&false) => {
+                    *pairs.last_mut().unwrap() = true;
+                    expect_value = true;
+                }
+                ']' if !expect_value && pairs.last() == Some(&true) => {
+                    pairs.pop();
+                }
+                _ => return error(pos, "unexpected character"),
+            }
+
+            if pairs.len() > 4 {
+                return error(pos, "numbers must not be nested inside more than 4 pairs");
+            }
+        }
+
+        if expect_value || !pairs.is_empty() {
+            return error(s.len(), "unexpected end of input");
+        }
+        Ok(Snailfish { leaves })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Reference implementation of snailfish numbers as a binary tree
+    #[derive(Clone, Debug)]
+    enum Node {
+        Leaf(u8),
+        Pair(Box<Node>, Box<Node>),
+    }
+
+    impl Node {
+        /// Tries to explode the leftmost pair under this node
+        ///  Returns whether a pair exploded, and the values to add to the
+        ///  leaves to the left and right.
+        fn explode(&mut self, depth: usize) -> Option<(u8, u8)> {
+            let Node::Pair(a, b) = self else {
+                return None;
+            };
+
+            if depth >= 4 {
+                let (Node::Leaf(l), Node::Leaf(r)) = (&**a, &**b) else {
+                    panic!("exploding pair must contain regular numbers");
+                };
+                let result = (*l, *r);
+                *self = Node::Leaf(0);
+                return Some(result);
+            }
+
+            if let Some((l, r)) = a.explode(depth + 1) {
+                *b.leaf(false) += r;
+                Some((l, 0))
+            } else if let Some((l, r)) = b.explode(depth + 1) {
+                *a.leaf(true) += l;
+                Some((0, r))
+            } else {
+                None
+            }
+        }
+
+        fn split(&mut self) -> bool {
+            match self {
+                &mut Node::Leaf(v) if v >= 10 => {
+                    *self = Node::Pair(
+                        Box::new(Node::Leaf(v / 2)),
+                        Box::new(Node::Leaf(v.div_ceil(2))),
+                    );
+                    true
+                }
+                Node::Leaf(_) => false,
+                Node::Pair(a, b) => a.split() || b.split(),
+            }
+        }
+
+        /// Returns the leftmost or rightmost leaf
+        fn leaf(&mut self, rightmost: bool) -> &mut u8 {
+            match self {
+                Node::Leaf(v) => v,
+                Node::Pair(l, _) if !rightmost => l.leaf(rightmost),
+                Node::Pair(_, r) => r.leaf(rightmost),
+            }
+        }
+
+        fn add(self, other: Node) -> Node {
+            let mut result = Node::Pair(Box::new(self), Box::new(other));
+            while result.explode(0).is_some() || result.split() {}
+            result
+        }
+
+        fn magnitude(&self) -> u32 {
+            match self {
+                Node::Leaf(v) => (*v).into(),
+                Node::Pair(l, r) => l.magnitude() * 3 + r.magnitude() * 2,
+            }
+        }
+    }
+
+    impl fmt::Display for Node {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                Node::Leaf(v) => write!(f, "{v}"),
+                Node::Pair(a, b) => write!(f, "[{a},{b}]"),
+            }
+        }
+    }
+
+    /// Simple xorshift random number generator
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self, bound: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % bound
+        }
+
+        /// Generates a random reduced tree
+        fn node(&mut self, depth: usize) -> Node {
+            if depth == 4 || self.next(3) == 0 {
+                Node::Leaf(self.next(10) as u8)
+            } else {
+                Node::Pair(
+                    Box::new(self.node(depth + 1)),
+                    Box::new(self.node(depth + 1)),
+                )
+            }
+        }
+    }
+
+    #[test]
+    fn flat_matches_tree() {
+        let mut rng = Rng(0x2021_1218);
+        for _ in 0..500 {
+            let trees: Vec<Node> = (0..4).map(|_| rng.node(0)).collect();
+            let flats: Vec<Snailfish> = trees
+                .iter()
+                .map(|t| t.to_string().parse().unwrap())
+                .collect();
+
+            for (tree, flat) in trees.iter().zip(&flats) {
+                assert_eq!(flat.to_string(), tree.to_string());
+                assert_eq!(flat.magnitude(), tree.magnitude());
+            }
+
+            let tree_sum = trees.into_iter().reduce(Node::add).unwrap();
+            let flat_sum: Snailfish = flats.into_iter().sum();
+            assert_eq!(flat_sum.to_string(), tree_sum.to_string());
+            assert_eq!(flat_sum.magnitude(), tree_sum.magnitude());
+        }
+    }
+
+    #[test]
+    fn addition() {
+        let a: Snailfish = "[[[[4,3],4],4],[7,[[8,4],9]]]".parse().unwrap();
+        let b: Snailfish = "[1,1]".parse().unwrap();
+        assert_eq!((a + b).to_string(), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let position = |s: &str| s.parse::<Snailfish>().unwrap_err().position;
+        assert_eq!(position("[1,2"), 4);
+        assert_eq!(position("[1 2]"), 3);
+        assert_eq!(position("[1,2] 3"), 6);
+        assert_eq!(position("[[[[[1,2],3],4],5],6]"), 4);
+        assert_eq!(position("[,2]"), 1);
+        assert_eq!(position("[1,10]"), 3);
+        assert_eq!(position("[[[[200,200],200],1],1]"), 4);
+        assert_eq!(position(""), 0);
+    }
+}
@@ -1,7 +1,50 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+/// A sequence stored as runs of (digit, count)
+type Runs = Vec<(u8, usize)>;
+
+/// Appends a digit to a run-length encoded sequence
+fn push_digit(runs: &mut Runs, digit: u8) {
+    match runs.last_mut() {
+        Some((last, count)) if *last == digit => *count += 1,
+        _ => runs.push((digit, 1)),
+    }
+}
+
+/// Performs one round of look-and-say
+///  Each run is read out as its count followed by its digit.
+fn look_and_say(runs: &Runs) -> Runs {
+    let mut result = Vec::with_capacity(runs.len() * 2);
+    for &(digit, count) in runs {
+        for count_digit in count.to_string().bytes() {
+            push_digit(&mut result, count_digit - b'0');
+        }
+        push_digit(&mut result, digit);
+    }
+    result
+}
+
+/// Returns the length of the sequence after the given number of rounds
+fn solve(input: &str, rounds: usize) -> String {
+    let mut runs = Vec::new();
+    for b in input.trim().bytes() {
+        push_digit(&mut runs, b - b'0');
+    }
+
+    for _ in 0..rounds {
+        runs = look_and_say(&runs);
+    }
+
+    runs.iter()
+        .map(|&(_, count)| count)
+        .sum::<usize>()
+        .to_string()
+}
+
+/// Length after 40 rounds
+pub fn star1(input: &str) -> String {
+    solve(input, 40)
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Length after 50 rounds
+pub fn star2(input: &str) -> String {
+    solve(input, 50)
 }
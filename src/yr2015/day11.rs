@@ -1,7 +1,76 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+/// Letters which may not appear in passwords
+const FORBIDDEN: [u8; 3] = [b'i', b'o', b'l'];
+
+/// Replaces the first forbidden letter with the next letter and resets the rest
+///  This skips every password containing the forbidden letter at once.
+fn skip_forbidden(password: &mut [u8]) {
+    if let Some(i) = password.iter().position(|c| FORBIDDEN.contains(c)) {
+        password[i] += 1;
+        password[i + 1..].fill(b'a');
+    }
+}
+
+/// Increments a password to the next one not containing any forbidden letters
+fn increment(password: &mut [u8]) {
+    for c in password.iter_mut().rev() {
+        if *c == b'z' {
+            *c = b'a';
+        } else {
+            *c += 1;
+            if FORBIDDEN.contains(c) {
+                *c += 1;
+            }
+            return;
+        }
+    }
+}
+
+/// Returns true if the password is valid (ignoring forbidden letters)
+fn is_valid(password: &[u8]) -> bool {
+    let straight = password
+        .windows(3)
+        .any(|w| w[1] == w[0] + 1 && w[2] == w[1] + 1);
+
+    // Pairs of different letters can never overlap
+    let mut pair_letters = password.windows(2).filter(|w| w[0] == w[1]).map(|w| w[0]);
+    let two_pairs = pair_letters
+        .next()
+        .is_some_and(|first| pair_letters.any(|c| c != first));
+
+    straight && two_pairs
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Finds the next valid password after the given one
+fn next_password(password: &str) -> String {
+    let mut password = password.trim().as_bytes().to_vec();
+    increment(&mut password);
+    skip_forbidden(&mut password);
+    while !is_valid(&password) {
+        increment(&mut password);
+    }
+
+    String::from_utf8(password).unwrap()
+}
+
+/// Santa's next password
+pub fn star1(input: &str) -> String {
+    next_password(input)
+}
+
+/// Santa's password after the next one
+pub fn star2(input: &str) -> String {
+    next_password(&next_password(input))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn different_pairs() {
+        assert!(is_valid(b"abcaabb"));
+        assert!(!is_valid(b"abcaaaa"));
+        assert!(!is_valid(b"aabcaa"));
+        assert_eq!(next_password("abcdefgh"), "abcdffaa");
+    }
 }
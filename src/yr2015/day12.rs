@@ -1,7 +1,40 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use crate::json::Value;
+
+/// Sums all the numbers in a JSON value, optionally ignoring red objects
+fn sum_numbers(value: &Value, ignore_red: bool) -> f64 {
+    match value {
+        Value::Number(n) => *n,
+        Value::Array(items) => items.iter().map(|v| sum_numbers(v, ignore_red)).sum(),
+        Value::Object(members) => {
+            let red = Value::String("red".to_string());
+            if ignore_red && members.iter().any(|(_, v)| *v == red) {
+                0.0
+            } else {
+                members
+                    .iter()
+                    .map(|(_, v)| sum_numbers(v, ignore_red))
+                    .sum()
+            }
+        }
+        _ => 0.0,
+    }
+}
+
+/// Parses the document and sums its numbers
+fn solve(input: &str, ignore_red: bool) -> String {
+    let document: Value = input
+        .trim()
+        .parse()
+        .unwrap_or_else(|e| panic!("invalid JSON: {e}"));
+    (sum_numbers(&document, ignore_red) as i64).to_string()
+}
+
+/// Sum of all numbers in the document
+pub fn star1(input: &str) -> String {
+    solve(input, false)
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Sum of all numbers in the document, ignoring objects containing "red"
+pub fn star2(input: &str) -> String {
+    solve(input, true)
 }
@@ -1,7 +1,42 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use super::tsp::{Objective, held_karp};
+use std::collections::HashMap;
+
+/// Parses the input into a matrix of the combined happiness of each pair of guests
+fn parse_happiness(input: &str) -> Vec<Vec<i32>> {
+    let mut guests: HashMap<&str, usize> = HashMap::new();
+    let mut changes = Vec::new();
+    for line in input.lines() {
+        let words: Vec<&str> = line.trim_end_matches('.').split_whitespace().collect();
+        let amount: i32 = words[3].parse().unwrap();
+        let change = if words[2] == "lose" { -amount } else { amount };
+        let mut guest_id = |name| {
+            let next_id = guests.len();
+            *guests.entry(name).or_insert(next_id)
+        };
+
+        changes.push((guest_id(words[0]), guest_id(words[10]), change));
+    }
+
+    let mut happiness = vec![vec![0; guests.len()]; guests.len()];
+    for (a, b, change) in changes {
+        happiness[a][b] += change;
+        happiness[b][a] += change;
+    }
+    happiness
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Happiness of the optimal seating arrangement
+pub fn star1(input: &str) -> String {
+    held_karp(&parse_happiness(input), Objective::Maximise, true).to_string()
+}
+
+/// Happiness of the optimal seating arrangement including yourself
+pub fn star2(input: &str) -> String {
+    let mut happiness = parse_happiness(input);
+    for row in &mut happiness {
+        row.push(0);
+    }
+    happiness.push(vec![0; happiness.len() + 1]);
+
+    held_karp(&happiness, Objective::Maximise, true).to_string()
 }
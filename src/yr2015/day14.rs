@@ -1,7 +1,69 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+/// A reindeer's flying abilities
+struct Reindeer {
+    speed: u32,
+    fly_time: u32,
+    rest_time: u32,
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+impl Reindeer {
+    /// Returns the distance travelled after the given number of seconds
+    fn distance(&self, time: u32) -> u32 {
+        let period = self.fly_time + self.rest_time;
+        let flying = time / period * self.fly_time + (time % period).min(self.fly_time);
+        flying * self.speed
+    }
+}
+
+/// Parses the input into the race duration and the list of reindeer
+///  The race duration can optionally be given on the first line.
+fn parse_input(input: &str) -> (u32, Vec<Reindeer>) {
+    let mut lines = input.lines().peekable();
+    let duration = match lines.peek().and_then(|l| l.trim().parse().ok()) {
+        Some(duration) => {
+            lines.next();
+            duration
+        }
+        None => 2503,
+    };
+
+    let reindeer = lines
+        .map(|line| {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            Reindeer {
+                speed: words[3].parse().unwrap(),
+                fly_time: words[6].parse().unwrap(),
+                rest_time: words[13].parse().unwrap(),
+            }
+        })
+        .collect();
+
+    (duration, reindeer)
+}
+
+/// Distance travelled by the winning reindeer
+pub fn star1(input: &str) -> String {
+    let (duration, reindeer) = parse_input(input);
+    reindeer
+        .iter()
+        .map(|r| r.distance(duration))
+        .max()
+        .unwrap()
+        .to_string()
+}
+
+/// Points of the winning reindeer when the leader gets a point every second
+pub fn star2(input: &str) -> String {
+    let (duration, reindeer) = parse_input(input);
+    let mut points = vec![0; reindeer.len()];
+    for time in 1..=duration {
+        let distances: Vec<u32> = reindeer.iter().map(|r| r.distance(time)).collect();
+        let lead = *distances.iter().max().unwrap();
+        for (p, &d) in points.iter_mut().zip(&distances) {
+            if d == lead {
+                *p += 1;
+            }
+        }
+    }
+
+    points.iter().max().unwrap().to_string()
 }
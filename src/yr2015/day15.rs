@@ -1,7 +1,72 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+/// Total teaspoons of ingredients in the cookie
+const TEASPOONS: i64 = 100;
+
+/// Parses the properties of each ingredient (the last property is calories)
+fn parse_input(input: &str) -> Vec<Vec<i64>> {
+    input
+        .lines()
+        .map(|line| {
+            line.split(',')
+                .map(|prop| prop.split_whitespace().last().unwrap().parse().unwrap())
+                .collect()
+        })
+        .collect()
+}
+
+/// Calls visit with every way of dividing total teaspoons between count ingredients
+fn for_each_partition(
+    count: usize,
+    total: i64,
+    amounts: &mut Vec<i64>,
+    visit: &mut impl FnMut(&[i64]),
+) {
+    if amounts.len() + 1 == count {
+        amounts.push(total);
+        visit(amounts);
+        amounts.pop();
+        return;
+    }
+
+    for amount in 0..=total {
+        amounts.push(amount);
+        for_each_partition(count, total - amount, amounts, visit);
+        amounts.pop();
+    }
+}
+
+/// Finds the highest scoring cookie, optionally with a fixed number of calories
+fn best_cookie(input: &str, calories: Option<i64>) -> i64 {
+    let ingredients = parse_input(input);
+    let properties = ingredients[0].len();
+    let mut best = 0;
+
+    for_each_partition(
+        ingredients.len(),
+        TEASPOONS,
+        &mut Vec::new(),
+        &mut |amounts| {
+            let totals: Vec<i64> = (0..properties)
+                .map(|p| {
+                    let total: i64 = ingredients.iter().zip(amounts).map(|(i, a)| i[p] * a).sum();
+                    total.max(0)
+                })
+                .collect();
+
+            if calories.is_none_or(|c| totals[properties - 1] == c) {
+                best = best.max(totals[..properties - 1].iter().product());
+            }
+        },
+    );
+
+    best
+}
+
+/// Score of the highest scoring cookie
+pub fn star1(input: &str) -> String {
+    best_cookie(input, None).to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Score of the highest scoring cookie with 500 calories
+pub fn star2(input: &str) -> String {
+    best_cookie(input, Some(500)).to_string()
 }
@@ -1,7 +1,64 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+/// A condition on the number of a compound remembered about a Sue
+#[derive(Clone, Copy, Debug)]
+enum Predicate {
+    Exactly(u32),
+    GreaterThan(u32),
+    FewerThan(u32),
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+impl Predicate {
+    /// Returns true if the value satisfies this predicate
+    fn matches(self, value: u32) -> bool {
+        match self {
+            Predicate::Exactly(n) => value == n,
+            Predicate::GreaterThan(n) => value > n,
+            Predicate::FewerThan(n) => value < n,
+        }
+    }
+}
+
+/// The results of the ticker tape analysis
+const ANALYSIS: [(&str, u32); 10] = [
+    ("children", 3),
+    ("cats", 7),
+    ("samoyeds", 2),
+    ("pomeranians", 3),
+    ("akitas", 0),
+    ("vizslas", 0),
+    ("goldfish", 5),
+    ("trees", 3),
+    ("cars", 2),
+    ("perfumes", 1),
+];
+
+/// Finds the number of the Sue whose compounds all satisfy the predicates
+fn find_sue(input: &str, predicate: impl Fn(&str, u32) -> Predicate) -> String {
+    input
+        .lines()
+        .find_map(|line| {
+            let (name, compounds) = line.split_once(": ").unwrap();
+            compounds
+                .split(", ")
+                .all(|compound| {
+                    let (compound, value) = compound.split_once(": ").unwrap();
+                    let (_, expected) = ANALYSIS.iter().find(|(c, _)| *c == compound).unwrap();
+                    predicate(compound, *expected).matches(value.parse().unwrap())
+                })
+                .then(|| name.trim_start_matches("Sue ").to_string())
+        })
+        .unwrap()
+}
+
+/// Number of the Sue who sent the gift
+pub fn star1(input: &str) -> String {
+    find_sue(input, |_, expected| Predicate::Exactly(expected))
+}
+
+/// Number of the Sue who sent the gift using the outdated retroencabulator
+pub fn star2(input: &str) -> String {
+    find_sue(input, |compound, expected| match compound {
+        "cats" | "trees" => Predicate::GreaterThan(expected),
+        "pomeranians" | "goldfish" => Predicate::FewerThan(expected),
+        _ => Predicate::Exactly(expected),
+    })
 }
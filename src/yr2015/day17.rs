@@ -1,7 +1,31 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use super::subsets::for_each_subset_sum;
+
+/// Parses the containers and returns the number of combinations of each size
+///  which hold exactly the eggnog
+fn combinations_by_size(input: &str) -> Vec<usize> {
+    let containers: Vec<u64> = input.lines().map(|l| l.trim().parse().unwrap()).collect();
+    let eggnog = if containers.len() <= 5 { 25 } else { 150 };
+
+    let mut counts = vec![0; containers.len() + 1];
+    for_each_subset_sum(&containers, eggnog, containers.len(), &mut |subset| {
+        counts[subset.len()] += 1;
+    });
+    counts
+}
+
+/// Number of combinations of containers which fit the eggnog
+pub fn star1(input: &str) -> String {
+    combinations_by_size(input)
+        .iter()
+        .sum::<usize>()
+        .to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Number of combinations using the minimum number of containers
+pub fn star2(input: &str) -> String {
+    combinations_by_size(input)
+        .into_iter()
+        .find(|&c| c > 0)
+        .unwrap()
+        .to_string()
 }
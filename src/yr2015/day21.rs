@@ -1,7 +1,97 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use itertools::Itertools;
+
+/// Hit points the player starts with
+const PLAYER_HP: i32 = 100;
+
+/// An item in the shop as (cost, damage, armor)
+type Item = (i32, i32, i32);
+
+const WEAPONS: [Item; 5] = [(8, 4, 0), (10, 5, 0), (25, 6, 0), (40, 7, 0), (74, 8, 0)];
+const ARMOR: [Item; 5] = [(13, 0, 1), (31, 0, 2), (53, 0, 3), (75, 0, 4), (102, 0, 5)];
+const RINGS: [Item; 6] = [
+    (25, 1, 0),
+    (50, 2, 0),
+    (100, 3, 0),
+    (20, 0, 1),
+    (40, 0, 2),
+    (80, 0, 3),
+];
+
+/// Stats of a fighter
+struct Stats {
+    hp: i32,
+    damage: i32,
+    armor: i32,
+}
+
+impl Stats {
+    /// Returns the number of turns this fighter takes to defeat the other
+    fn turns_to_defeat(&self, other: &Stats) -> i32 {
+        let per_turn = (self.damage - other.armor).max(1);
+        (other.hp + per_turn - 1) / per_turn
+    }
+}
+
+/// Parses the boss stats
+fn parse_boss(input: &str) -> Stats {
+    let values: Vec<i32> = input
+        .lines()
+        .map(|line| line.split_once(": ").unwrap().1.parse().unwrap())
+        .collect();
+
+    Stats {
+        hp: values[0],
+        damage: values[1],
+        armor: values[2],
+    }
+}
+
+/// Returns an iterator over all valid equipment loadouts as (cost, player wins)
+fn loadouts(input: &str) -> impl Iterator<Item = (i32, bool)> {
+    let boss = parse_boss(input);
+    let armor_choices = (0..=1).flat_map(|n| ARMOR.iter().combinations(n));
+    let ring_choices = (0..=2).flat_map(|n| RINGS.iter().combinations(n));
+
+    WEAPONS
+        .iter()
+        .cartesian_product(armor_choices.collect_vec())
+        .cartesian_product(ring_choices.collect_vec())
+        .map(move |((weapon, armor), rings)| {
+            let (cost, damage, armor) = std::iter::once(weapon)
+                .chain(armor)
+                .chain(rings)
+                .fold((0, 0, 0), |acc, item| {
+                    (acc.0 + item.0, acc.1 + item.1, acc.2 + item.2)
+                });
+
+            let player = Stats {
+                hp: PLAYER_HP,
+                damage,
+                armor,
+            };
+            (
+                cost,
+                player.turns_to_defeat(&boss) <= boss.turns_to_defeat(&player),
+            )
+        })
+}
+
+/// Least gold spent to win the fight
+pub fn star1(input: &str) -> String {
+    loadouts(input)
+        .filter(|&(_, win)| win)
+        .map(|(cost, _)| cost)
+        .min()
+        .unwrap()
+        .to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Most gold spent while still losing the fight
+pub fn star2(input: &str) -> String {
+    loadouts(input)
+        .filter(|&(_, win)| !win)
+        .map(|(cost, _)| cost)
+        .max()
+        .unwrap()
+        .to_string()
 }
@@ -1,7 +1,205 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::options;
+
+/// A spell the wizard can cast
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+enum Spell {
+    MagicMissile,
+    Drain,
+    Shield,
+    Poison,
+    Recharge,
+}
+
+impl Spell {
+    const ALL: [Spell; 5] = [
+        Spell::MagicMissile,
+        Spell::Drain,
+        Spell::Shield,
+        Spell::Poison,
+        Spell::Recharge,
+    ];
+
+    /// Mana cost of this spell
+    fn cost(self) -> i32 {
+        match self {
+            Spell::MagicMissile => 53,
+            Spell::Drain => 73,
+            Spell::Shield => 113,
+            Spell::Poison => 173,
+            Spell::Recharge => 229,
+        }
+    }
+}
+
+/// The state of the battle at the start of the player's turn
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+struct Battle {
+    player_hp: i32,
+    mana: i32,
+    boss_hp: i32,
+    boss_damage: i32,
+    shield: u8,
+    poison: u8,
+    recharge: u8,
+}
+
+/// The outcome of a round of the battle
+enum Outcome {
+    Continue(Battle),
+    /// The boss was defeated, possibly before the player cast their spell
+    Won {
+        cast: bool,
+    },
+    Lost,
+}
+
+impl Battle {
+    /// Applies active effects at the start of a turn, returning the player's armor
+    fn apply_effects(&mut self) -> i32 {
+        let mut armor = 0;
+        if self.shield > 0 {
+            armor = 7;
+            self.shield -= 1;
+        }
+        if self.poison > 0 {
+            self.boss_hp -= 3;
+            self.poison -= 1;
+        }
+        if self.recharge > 0 {
+            self.mana += 101;
+            self.recharge -= 1;
+        }
+        armor
+    }
+
+    /// Plays the player's turn (casting the given spell) and the boss's turn
+    ///  Returns None if the spell cannot be cast.
+    fn round(mut self, spell: Spell, hard: bool) -> Option<Outcome> {
+        // Player turn
+        if hard {
+            self.player_hp -= 1;
+            if self.player_hp <= 0 {
+                return Some(Outcome::Lost);
+            }
+        }
+
+        self.apply_effects();
+        if self.boss_hp <= 0 {
+            return Some(Outcome::Won { cast: false });
+        }
+
+        if spell.cost() > self.mana {
+            return None;
+        }
+        self.mana -= spell.cost();
+
+        match spell {
+            Spell::MagicMissile => self.boss_hp -= 4,
+            Spell::Drain => {
+                self.boss_hp -= 2;
+                self.player_hp += 2;
+            }
+            Spell::Shield if self.shield == 0 => self.shield = 6,
+            Spell::Poison if self.poison == 0 => self.poison = 6,
+            Spell::Recharge if self.recharge == 0 => self.recharge = 5,
+            _ => return None,
+        }
+
+        // Boss turn
+        let armor = self.apply_effects();
+        if self.boss_hp <= 0 {
+            return Some(Outcome::Won { cast: true });
+        }
+
+        self.player_hp -= (self.boss_damage - armor).max(1);
+        if self.player_hp <= 0 {
+            Some(Outcome::Lost)
+        } else {
+            Some(Outcome::Continue(self))
+        }
+    }
+}
+
+/// Parses the boss stats into the initial battle state
+fn parse_input(input: &str) -> Battle {
+    let values: Vec<i32> = input
+        .lines()
+        .map(|line| line.split_once(": ").unwrap().1.parse().unwrap())
+        .collect();
+
+    Battle {
+        player_hp: 50,
+        mana: 500,
+        boss_hp: values[0],
+        boss_damage: values[1],
+        shield: 0,
+        poison: 0,
+        recharge: 0,
+    }
+}
+
+/// Finds the cheapest winning sequence of spells using Dijkstra's algorithm
+///  Returns the mana spent and the spells cast.
+fn cheapest_win(initial: Battle, hard: bool) -> Option<(i32, Vec<Spell>)> {
+    let mut queue = BinaryHeap::from([Reverse((0, Some(initial)))]);
+    let mut best = HashMap::from([(Some(initial), 0)]);
+    let mut previous: HashMap<Option<Battle>, (Battle, Option<Spell>)> = HashMap::new();
+
+    // None is used as the winning state
+    while let Some(Reverse((spent, state))) = queue.pop() {
+        let Some(battle) = state else {
+            let mut spells = Vec::new();
+            let mut current = None;
+            while let Some(&(prev, spell)) = previous.get(&current) {
+                spells.extend(spell);
+                current = Some(prev);
+            }
+            spells.reverse();
+            return Some((spent, spells));
+        };
+
+        if spent > best[&state] {
+            continue;
+        }
+
+        for spell in Spell::ALL {
+            let (next, cast) = match battle.round(spell, hard) {
+                Some(Outcome::Continue(next)) => (Some(next), true),
+                Some(Outcome::Won { cast }) => (None, cast),
+                Some(Outcome::Lost) | None => continue,
+            };
+
+            let next_spent = if cast { spent + spell.cost() } else { spent };
+            if best.get(&next).is_none_or(|&b| next_spent < b) {
+                best.insert(next, next_spent);
+                previous.insert(next, (battle, cast.then_some(spell)));
+                queue.push(Reverse((next_spent, next)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds the least mana needed to win
+///  In verbose mode, the winning spell sequence is printed.
+fn solve(input: &str, hard: bool) -> String {
+    let (spent, spells) = cheapest_win(parse_input(input), hard).expect("cannot win");
+    if options::verbose() {
+        eprintln!("{spells:?}");
+    }
+    spent.to_string()
+}
+
+/// Least mana spent to win the fight
+pub fn star1(input: &str) -> String {
+    solve(input, false)
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Least mana spent to win the fight on hard mode
+pub fn star2(input: &str) -> String {
+    solve(input, true)
 }
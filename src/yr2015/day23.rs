@@ -1,7 +1,84 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+/// A single instruction for the Turing lock
+#[derive(Clone, Copy, Debug)]
+enum Instruction {
+    Half(usize),
+    Triple(usize),
+    Increment(usize),
+    Jump(isize),
+    JumpIfEven(usize, isize),
+    JumpIfOne(usize, isize),
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Parses a register name into a register index
+fn parse_register(s: &str) -> usize {
+    match s.trim_end_matches(',') {
+        "a" => 0,
+        "b" => 1,
+        r => panic!("invalid register {r}"),
+    }
+}
+
+/// Parses the input program
+fn parse_program(input: &str) -> Vec<Instruction> {
+    input
+        .lines()
+        .map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            match parts[..] {
+                ["hlf", r] => Instruction::Half(parse_register(r)),
+                ["tpl", r] => Instruction::Triple(parse_register(r)),
+                ["inc", r] => Instruction::Increment(parse_register(r)),
+                ["jmp", offset] => Instruction::Jump(offset.parse().unwrap()),
+                ["jie", r, offset] => {
+                    Instruction::JumpIfEven(parse_register(r), offset.parse().unwrap())
+                }
+                ["jio", r, offset] => {
+                    Instruction::JumpIfOne(parse_register(r), offset.parse().unwrap())
+                }
+                _ => panic!("invalid instruction {line}"),
+            }
+        })
+        .collect()
+}
+
+/// Runs the program until it exits, returning the final registers
+fn run(program: &[Instruction], mut regs: [u64; 2]) -> [u64; 2] {
+    let mut pc = 0;
+    while let Some(&instruction) = program.get(pc) {
+        let offset = match instruction {
+            Instruction::Half(r) => {
+                regs[r] /= 2;
+                1
+            }
+            Instruction::Triple(r) => {
+                regs[r] *= 3;
+                1
+            }
+            Instruction::Increment(r) => {
+                regs[r] += 1;
+                1
+            }
+            Instruction::Jump(offset) => offset,
+            Instruction::JumpIfEven(r, offset) if regs[r].is_multiple_of(2) => offset,
+            Instruction::JumpIfOne(r, offset) if regs[r] == 1 => offset,
+            _ => 1,
+        };
+
+        match pc.checked_add_signed(offset) {
+            Some(new_pc) => pc = new_pc,
+            None => break,
+        }
+    }
+
+    regs
+}
+
+/// Value of register b after running the program
+pub fn star1(input: &str) -> String {
+    run(&parse_program(input), [0, 0])[1].to_string()
+}
+
+/// Value of register b after running the program with a starting at 1
+pub fn star2(input: &str) -> String {
+    run(&parse_program(input), [1, 0])[1].to_string()
 }
@@ -1,7 +1,67 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use std::ops::ControlFlow;
+
+use super::subsets::{for_each_subset_sum, try_for_each_subset_sum};
+
+/// Returns true if the items can be split into the given number of groups with equal weight
+fn can_split(items: &[u64], groups: u64) -> bool {
+    let total: u64 = items.iter().sum();
+    if !total.is_multiple_of(groups) {
+        return false;
+    }
+    if groups == 1 {
+        return true;
+    }
+
+    try_for_each_subset_sum(items, total / groups, items.len(), &mut |subset| {
+        let rest: Vec<u64> = (0..items.len())
+            .filter(|i| !subset.contains(i))
+            .map(|i| items[i])
+            .collect();
+        if can_split(&rest, groups - 1) {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+    .is_break()
+}
+
+/// Finds the quantum entanglement of the ideal first group of packages
+///  The first group must have the fewest packages, and then the smallest
+///  product of weights.
+fn best_entanglement(input: &str, groups: u64) -> u64 {
+    let packages: Vec<u64> = input.lines().map(|l| l.trim().parse().unwrap()).collect();
+    let target = packages.iter().sum::<u64>() / groups;
+
+    for max_len in 1..=packages.len() {
+        let mut best: Option<u64> = None;
+        for_each_subset_sum(&packages, target, max_len, &mut |subset| {
+            let entanglement = subset.iter().map(|&i| packages[i]).product();
+            if best.is_none_or(|b| entanglement < b) {
+                let rest: Vec<u64> = (0..packages.len())
+                    .filter(|i| !subset.contains(i))
+                    .map(|i| packages[i])
+                    .collect();
+                if can_split(&rest, groups - 1) {
+                    best = Some(entanglement);
+                }
+            }
+        });
+
+        if let Some(entanglement) = best {
+            return entanglement;
+        }
+    }
+
+    panic!("packages cannot be balanced")
+}
+
+/// Quantum entanglement of the first group when split into 3 groups
+pub fn star1(input: &str) -> String {
+    best_entanglement(input, 3).to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Quantum entanglement of the first group when split into 4 groups
+pub fn star2(input: &str) -> String {
+    best_entanglement(input, 4).to_string()
 }
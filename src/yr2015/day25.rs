@@ -1,3 +1,29 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+/// Calculates base ^ exp mod modulus
+fn mod_pow(mut base: u64, mut exp: u64, modulus: u64) -> u64 {
+    let mut result = 1;
+    base %= modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result
+}
+
+/// Code required for the weather machine
+pub fn star1(input: &str) -> String {
+    let numbers: Vec<u64> = input
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|n| !n.is_empty())
+        .map(|n| n.parse().unwrap())
+        .collect();
+    let (row, column) = (numbers[0], numbers[1]);
+
+    // Codes are filled in along diagonals, so find the index of this position
+    let diagonal = row + column - 1;
+    let index = diagonal * (diagonal - 1) / 2 + column - 1;
+
+    (20151125 * mod_pow(252533, index, 33554393) % 33554393).to_string()
 }
@@ -1,7 +1,36 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use crate::direction::Direction;
+use nalgebra::Vector2;
+use std::collections::HashSet;
+
+/// Delivers presents with the given number of santas taking turns to follow the directions
+///  Returns the number of houses receiving at least one present.
+fn deliver(input: &str, santas: usize) -> usize {
+    let mut positions = vec![Vector2::<i32>::zeros(); santas];
+    let mut visited = HashSet::from([Vector2::zeros()]);
+
+    for (i, c) in input.trim().chars().enumerate() {
+        let dir = match c {
+            '^' => Direction::North,
+            '>' => Direction::East,
+            'v' => Direction::South,
+            '<' => Direction::West,
+            _ => panic!("invalid direction {c}"),
+        };
+
+        let santa = &mut positions[i % santas];
+        *santa += dir.to_vec();
+        visited.insert(*santa);
+    }
+
+    visited.len()
+}
+
+/// Houses visited by Santa
+pub fn star1(input: &str) -> String {
+    deliver(input, 1).to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Houses visited by Santa and Robo-Santa
+pub fn star2(input: &str) -> String {
+    deliver(input, 2).to_string()
 }
@@ -1,7 +1,19 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use crate::md5::{find_suffix, leading_zero_nibbles};
+
+/// Finds the lowest positive number which produces a hash with the given number of leading zeros
+fn mine_advent_coin(input: &str, zeros: usize) -> String {
+    find_suffix(input.trim().as_bytes(), 1, |digest| {
+        leading_zero_nibbles(digest) >= zeros
+    })
+    .to_string()
+}
+
+/// Mine AdventCoins with five leading zeros
+pub fn star1(input: &str) -> String {
+    mine_advent_coin(input, 5)
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Mine AdventCoins with six leading zeros
+pub fn star2(input: &str) -> String {
+    mine_advent_coin(input, 6)
 }
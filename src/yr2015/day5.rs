@@ -1,7 +1,28 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use itertools::Itertools;
+
+/// Nice string rules for star 1
+fn is_nice1(s: &str) -> bool {
+    let vowels = s.chars().filter(|c| "aeiou".contains(*c)).count();
+    let double = s.chars().tuple_windows().any(|(a, b)| a == b);
+    let forbidden = ["ab", "cd", "pq", "xy"].iter().any(|f| s.contains(f));
+    vowels >= 3 && double && !forbidden
+}
+
+/// Nice string rules for star 2
+fn is_nice2(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    let repeated_pair = (0..bytes.len().saturating_sub(1))
+        .any(|i| bytes[i + 2..].windows(2).any(|w| w == &bytes[i..i + 2]));
+    let sandwich = bytes.windows(3).any(|w| w[0] == w[2]);
+    repeated_pair && sandwich
+}
+
+/// Number of nice strings
+pub fn star1(input: &str) -> String {
+    input.lines().filter(|l| is_nice1(l)).count().to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Number of nice strings using the new rules
+pub fn star2(input: &str) -> String {
+    input.lines().filter(|l| is_nice2(l)).count().to_string()
 }
@@ -1,7 +1,111 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use itertools::Itertools;
+
+/// An action applied to a rectangle of lights
+#[derive(Clone, Copy, Debug)]
+enum Action {
+    On,
+    Off,
+    Toggle,
+}
+
+/// A rectangle of lights with inclusive start and exclusive end coordinates
+#[derive(Clone, Copy, Debug)]
+struct Instruction {
+    action: Action,
+    start: (usize, usize),
+    end: (usize, usize),
+}
+
+/// Parses a coordinate pair
+fn parse_coord(s: &str) -> (usize, usize) {
+    let (x, y) = s.split_once(',').unwrap();
+    (x.parse().unwrap(), y.parse().unwrap())
+}
+
+/// Parses the list of instructions
+fn parse_input(input: &str) -> Vec<Instruction> {
+    input
+        .lines()
+        .map(|line| {
+            let (action, rest) = if let Some(rest) = line.strip_prefix("turn on ") {
+                (Action::On, rest)
+            } else if let Some(rest) = line.strip_prefix("turn off ") {
+                (Action::Off, rest)
+            } else {
+                (Action::Toggle, line.strip_prefix("toggle ").unwrap())
+            };
+
+            let (start, end) = rest.split_once(" through ").unwrap();
+            let (end_x, end_y) = parse_coord(end);
+            Instruction {
+                action,
+                start: parse_coord(start),
+                end: (end_x + 1, end_y + 1),
+            }
+        })
+        .collect()
+}
+
+/// Applies the instructions and returns the total brightness of all the lights
+///  Instead of tracking each light, the grid is split into blocks along
+///  every rectangle edge. All lights within a block behave identically.
+fn total_brightness(input: &str, apply: fn(u32, Action) -> u32) -> u64 {
+    let instructions = parse_input(input);
+    let boundaries = |axis: fn(&(usize, usize)) -> usize| -> Vec<usize> {
+        instructions
+            .iter()
+            .flat_map(|i| [axis(&i.start), axis(&i.end)])
+            .sorted_unstable()
+            .dedup()
+            .collect()
+    };
+    let xs = boundaries(|c| c.0);
+    let ys = boundaries(|c| c.1);
+
+    let mut blocks = vec![vec![0; ys.len()]; xs.len()];
+    for instruction in &instructions {
+        let x_range = xs.binary_search(&instruction.start.0).unwrap()
+            ..xs.binary_search(&instruction.end.0).unwrap();
+        let y_range = ys.binary_search(&instruction.start.1).unwrap()
+            ..ys.binary_search(&instruction.end.1).unwrap();
+
+        for column in &mut blocks[x_range] {
+            for block in &mut column[y_range.clone()] {
+                *block = apply(*block, instruction.action);
+            }
+        }
+    }
+
+    xs.iter()
+        .tuple_windows()
+        .zip(&blocks)
+        .flat_map(|((x1, x2), column)| {
+            ys.iter()
+                .tuple_windows()
+                .zip(column)
+                .map(move |((y1, y2), &brightness)| {
+                    ((x2 - x1) * (y2 - y1)) as u64 * u64::from(brightness)
+                })
+        })
+        .sum()
+}
+
+/// Number of lights lit after following the instructions
+pub fn star1(input: &str) -> String {
+    total_brightness(input, |light, action| match action {
+        Action::On => 1,
+        Action::Off => 0,
+        Action::Toggle => 1 - light,
+    })
+    .to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Total brightness after following the instructions
+pub fn star2(input: &str) -> String {
+    total_brightness(input, |light, action| match action {
+        Action::On => light + 1,
+        Action::Off => light.saturating_sub(1),
+        Action::Toggle => light + 2,
+    })
+    .to_string()
 }
@@ -1,7 +1,194 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// An input to a gate
+#[derive(Clone, Copy, Debug)]
+enum Operand<'a> {
+    Value(u16),
+    Wire(&'a str),
+}
+
+impl Operand<'_> {
+    fn parse(s: &str) -> Operand<'_> {
+        s.parse().map_or(Operand::Wire(s), Operand::Value)
+    }
+}
+
+/// A logic gate driving a single wire
+#[derive(Clone, Copy, Debug)]
+enum Gate<'a> {
+    Id(Operand<'a>),
+    And(Operand<'a>, Operand<'a>),
+    Or(Operand<'a>, Operand<'a>),
+    LShift(Operand<'a>, Operand<'a>),
+    RShift(Operand<'a>, Operand<'a>),
+    Not(Operand<'a>),
+}
+
+impl<'a> Gate<'a> {
+    /// Returns the operands of this gate
+    fn operands(&self) -> Vec<Operand<'a>> {
+        match *self {
+            Gate::Id(a) | Gate::Not(a) => vec![a],
+            Gate::And(a, b) | Gate::Or(a, b) | Gate::LShift(a, b) | Gate::RShift(a, b) => {
+                vec![a, b]
+            }
+        }
+    }
+
+    /// Calculates the output of this gate given a function to read wire values
+    fn apply(&self, read: impl Fn(Operand<'a>) -> u16) -> u16 {
+        match *self {
+            Gate::Id(a) => read(a),
+            Gate::And(a, b) => read(a) & read(b),
+            Gate::Or(a, b) => read(a) | read(b),
+            Gate::LShift(a, b) => read(a).checked_shl(read(b).into()).unwrap_or(0),
+            Gate::RShift(a, b) => read(a).checked_shr(read(b).into()).unwrap_or(0),
+            Gate::Not(a) => !read(a),
+        }
+    }
+}
+
+/// Errors which occur when evaluating a circuit
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum CircuitError {
+    /// A wire is not connected to anything
+    Unconnected(String),
+    /// A wire depends on its own value
+    Cycle(String),
+}
+
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CircuitError::Unconnected(wire) => write!(f, "wire {wire} has no input"),
+            CircuitError::Cycle(wire) => write!(f, "wire {wire} is part of a cycle"),
+        }
+    }
+}
+
+/// A circuit of gates, evaluated lazily
+struct Circuit<'a> {
+    gates: HashMap<&'a str, Gate<'a>>,
+    values: HashMap<&'a str, u16>,
+}
+
+impl<'a> Circuit<'a> {
+    /// Parses the circuit instructions
+    fn parse(input: &'a str) -> Circuit<'a> {
+        let gates = input
+            .lines()
+            .map(|line| {
+                let (expr, wire) = line.split_once(" -> ").unwrap();
+                let parts: Vec<&str> = expr.split_whitespace().collect();
+                let gate = match parts[..] {
+                    [a] => Gate::Id(Operand::parse(a)),
+                    ["NOT", a] => Gate::Not(Operand::parse(a)),
+                    [a, op, b] => {
+                        let (a, b) = (Operand::parse(a), Operand::parse(b));
+                        match op {
+                            "AND" => Gate::And(a, b),
+                            "OR" => Gate::Or(a, b),
+                            "LSHIFT" => Gate::LShift(a, b),
+                            "RSHIFT" => Gate::RShift(a, b),
+                            _ => panic!("unknown gate {op}"),
+                        }
+                    }
+                    _ => panic!("invalid instruction {line}"),
+                };
+
+                (wire.trim(), gate)
+            })
+            .collect();
+
+        Circuit {
+            gates,
+            values: HashMap::new(),
+        }
+    }
+
+    /// Replaces the input of a wire with a fixed signal and resets all other signals
+    fn override_wire(&mut self, wire: &'a str, value: u16) {
+        self.gates.insert(wire, Gate::Id(Operand::Value(value)));
+        self.values.clear();
+    }
+
+    /// Evaluates the signal on a wire
+    ///  This uses an explicit stack so deep circuits cannot overflow the
+    ///  call stack. Wires which are started but not finished are tracked in
+    ///  order to detect cycles.
+    fn evaluate(&mut self, wire: &'a str) -> Result<u16, CircuitError> {
+        let mut stack = vec![wire];
+        let mut in_progress = HashSet::new();
+
+        while let Some(&current) = stack.last() {
+            if self.values.contains_key(current) {
+                stack.pop();
+                continue;
+            }
+
+            let gate = *self
+                .gates
+                .get(current)
+                .ok_or_else(|| CircuitError::Unconnected(current.to_string()))?;
+            let pending: Vec<&str> = gate
+                .operands()
+                .into_iter()
+                .filter_map(|op| match op {
+                    Operand::Wire(w) if !self.values.contains_key(w) => Some(w),
+                    _ => None,
+                })
+                .collect();
+
+            if pending.is_empty() {
+                let value = gate.apply(|op| match op {
+                    Operand::Value(v) => v,
+                    Operand::Wire(w) => self.values[w],
+                });
+                self.values.insert(current, value);
+                in_progress.remove(current);
+                stack.pop();
+            } else {
+                if let Some(w) = pending.iter().find(|w| in_progress.contains(*w)) {
+                    return Err(CircuitError::Cycle(w.to_string()));
+                }
+
+                in_progress.insert(current);
+                stack.extend(pending);
+            }
+        }
+
+        Ok(self.values[wire])
+    }
+}
+
+/// Evaluates a wire, panicking with a useful message on failure
+fn evaluate_or_panic<'a>(circuit: &mut Circuit<'a>, wire: &'a str) -> u16 {
+    circuit
+        .evaluate(wire)
+        .unwrap_or_else(|e| panic!("error evaluating circuit: {e}"))
+}
+
+/// Signal on wire a
+pub fn star1(input: &str) -> String {
+    evaluate_or_panic(&mut Circuit::parse(input), "a").to_string()
+}
+
+/// Signal on wire a after overriding b with the original signal on a
+pub fn star2(input: &str) -> String {
+    let mut circuit = Circuit::parse(input);
+    let a = evaluate_or_panic(&mut circuit, "a");
+    circuit.override_wire("b", a);
+    evaluate_or_panic(&mut circuit, "a").to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cycle() {
+        let mut circuit = Circuit::parse("x AND 1 -> y\ny OR 2 -> z\nNOT z -> x\nz -> a");
+        assert_eq!(circuit.evaluate("a"), Err(CircuitError::Cycle("z".into())));
+    }
 }
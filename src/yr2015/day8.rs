@@ -1,7 +1,38 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+/// Returns the number of characters in memory represented by a string literal
+fn decoded_len(literal: &str) -> usize {
+    let inner = &literal.as_bytes()[1..literal.len() - 1];
+    let mut len = 0;
+    let mut i = 0;
+    while i < inner.len() {
+        i += match &inner[i..] {
+            [b'\\', b'x', ..] => 4,
+            [b'\\', ..] => 2,
+            _ => 1,
+        };
+        len += 1;
+    }
+    len
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Returns the length of a string after encoding it as a new string literal
+fn encoded_len(s: &str) -> usize {
+    2 + s.len() + s.chars().filter(|&c| c == '"' || c == '\\').count()
+}
+
+/// Characters of code minus characters in memory
+pub fn star1(input: &str) -> String {
+    input
+        .lines()
+        .map(|line| line.len() - decoded_len(line))
+        .sum::<usize>()
+        .to_string()
+}
+
+/// Characters of the encoded strings minus characters of code
+pub fn star2(input: &str) -> String {
+    input
+        .lines()
+        .map(|line| encoded_len(line) - line.len())
+        .sum::<usize>()
+        .to_string()
 }
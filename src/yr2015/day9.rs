@@ -1,7 +1,55 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use super::tsp::{Objective, held_karp};
+use std::collections::HashMap;
+
+/// Parses the input into a distance matrix
+fn parse_distances(input: &str) -> Vec<Vec<i32>> {
+    let mut cities: HashMap<&str, usize> = HashMap::new();
+    let mut edges = Vec::new();
+    for line in input.lines() {
+        let (route, distance) = line.split_once(" = ").unwrap();
+        let (from, to) = route.split_once(" to ").unwrap();
+        let mut city_id = |name| {
+            let next_id = cities.len();
+            *cities.entry(name).or_insert(next_id)
+        };
+
+        edges.push((city_id(from), city_id(to), distance.parse().unwrap()));
+    }
+
+    let mut distances = vec![vec![None; cities.len()]; cities.len()];
+    for (a, b, distance) in edges {
+        distances[a][b] = Some(distance);
+        distances[b][a] = Some(distance);
+    }
+
+    // The solver needs a distance between every pair of cities
+    let names: HashMap<usize, &str> = cities.iter().map(|(&name, &id)| (id, name)).collect();
+    distances
+        .iter()
+        .enumerate()
+        .map(|(a, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(b, &distance)| {
+                    distance.unwrap_or_else(|| {
+                        if a == b {
+                            0
+                        } else {
+                            panic!("no distance between {} and {}", names[&a], names[&b])
+                        }
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Shortest route visiting every city
+pub fn star1(input: &str) -> String {
+    held_karp(&parse_distances(input), Objective::Minimise, false).to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Longest route visiting every city
+pub fn star2(input: &str) -> String {
+    held_karp(&parse_distances(input), Objective::Maximise, false).to_string()
 }
@@ -25,6 +25,8 @@ mod day6;
 mod day7;
 mod day8;
 mod day9;
+mod subsets;
+mod tsp;
 
 use crate::StarFunction;
 use crate::StarId;
@@ -0,0 +1,94 @@
+//! Subset sum enumeration used in days 17 and 24
+
+use std::convert::Infallible;
+use std::ops::ControlFlow;
+
+/// Calls visit with every subset of items (by index) which sums to target
+///  Only subsets with at most max_len items are visited. Subsets are visited
+///  in lexicographic order of their (increasing) indices.
+pub fn for_each_subset_sum(
+    items: &[u64],
+    target: u64,
+    max_len: usize,
+    visit: &mut impl FnMut(&[usize]),
+) {
+    let ControlFlow::Continue(()) =
+        try_for_each_subset_sum::<Infallible>(items, target, max_len, &mut |subset| {
+            visit(subset);
+            ControlFlow::Continue(())
+        });
+}
+
+/// Like for_each_subset_sum, but stops early if visit returns Break
+///  The Break from visit is returned if the enumeration stopped early.
+pub fn try_for_each_subset_sum<B>(
+    items: &[u64],
+    target: u64,
+    max_len: usize,
+    visit: &mut impl FnMut(&[usize]) -> ControlFlow<B>,
+) -> ControlFlow<B> {
+    fn recurse<B>(
+        items: &[u64],
+        start: usize,
+        remaining: u64,
+        max_len: usize,
+        chosen: &mut Vec<usize>,
+        visit: &mut impl FnMut(&[usize]) -> ControlFlow<B>,
+    ) -> ControlFlow<B> {
+        if remaining == 0 {
+            return visit(chosen);
+        }
+        if chosen.len() == max_len {
+            return ControlFlow::Continue(());
+        }
+
+        for i in start..items.len() {
+            if items[i] <= remaining {
+                chosen.push(i);
+                recurse(items, i + 1, remaining - items[i], max_len, chosen, visit)?;
+                chosen.pop();
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    recurse(items, 0, target, max_len, &mut Vec::new(), visit)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn subsets(items: &[u64], target: u64, max_len: usize) -> Vec<Vec<usize>> {
+        let mut result = Vec::new();
+        for_each_subset_sum(items, target, max_len, &mut |subset| {
+            result.push(subset.to_vec());
+        });
+        result
+    }
+
+    #[test]
+    fn enumeration() {
+        let items = [1, 2, 3, 4, 5];
+        assert_eq!(subsets(&items, 5, 5), [vec![0, 3], vec![1, 2], vec![4]]);
+        assert_eq!(subsets(&items, 5, 1), [vec![4]]);
+        assert_eq!(subsets(&items, 16, 5), Vec::<Vec<usize>>::new());
+
+        // Equal items are still different subsets, but each is visited once
+        assert_eq!(
+            subsets(&[2, 2, 2], 4, 3),
+            [vec![0, 1], vec![0, 2], vec![1, 2]]
+        );
+    }
+
+    #[test]
+    fn stops_early() {
+        let mut visited = 0;
+        let flow = try_for_each_subset_sum(&[1, 1, 1, 1], 2, 4, &mut |subset| {
+            visited += 1;
+            ControlFlow::Break(subset.to_vec())
+        });
+        assert_eq!(flow, ControlFlow::Break(vec![0, 1]));
+        assert_eq!(visited, 1);
+    }
+}
@@ -0,0 +1,81 @@
+//! Exact travelling salesman solver used in days 9 and 13
+
+/// Whether to find the shortest or the longest route
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Objective {
+    Minimise,
+    Maximise,
+}
+
+impl Objective {
+    /// Returns the better of two route lengths
+    fn best(self, a: i32, b: i32) -> i32 {
+        match self {
+            Objective::Minimise => a.min(b),
+            Objective::Maximise => a.max(b),
+        }
+    }
+}
+
+/// Finds the optimal route visiting every node using the Held-Karp algorithm
+///  distances[a][b] is the cost of travelling from node a to node b. Every
+///  pair of nodes must be connected, since there is no way to represent a
+///  missing edge. If cycle is true, the route must return to its starting
+///  node. Otherwise it may start and end anywhere.
+pub fn held_karp(distances: &[Vec<i32>], objective: Objective, cycle: bool) -> i32 {
+    let n = distances.len();
+    if n <= 1 {
+        return 0;
+    }
+
+    // best[mask][last] = best cost of a route visiting mask and ending at last
+    //  Cycles can always start at node 0 without loss of generality.
+    let mut best: Vec<Vec<Option<i32>>> = vec![vec![None; n]; 1 << n];
+    for start in 0..if cycle { 1 } else { n } {
+        best[1 << start][start] = Some(0);
+    }
+
+    for mask in 1..1usize << n {
+        for last in 0..n {
+            let Some(cost) = best[mask][last] else {
+                continue;
+            };
+
+            for next in (0..n).filter(|&next| mask & (1 << next) == 0) {
+                let new_cost = cost + distances[last][next];
+                let entry = &mut best[mask | (1 << next)][next];
+                *entry = Some(entry.map_or(new_cost, |old| objective.best(old, new_cost)));
+            }
+        }
+    }
+
+    best[(1 << n) - 1]
+        .iter()
+        .enumerate()
+        .filter_map(|(last, &cost)| cost.map(|c| if cycle { c + distances[last][0] } else { c }))
+        .reduce(|a, b| objective.best(a, b))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn objectives() {
+        let distances = vec![
+            vec![0, 1, 4, 2],
+            vec![1, 0, 3, 5],
+            vec![4, 3, 0, 1],
+            vec![2, 5, 1, 0],
+        ];
+
+        // Best open routes are 1-0-3-2 and 0-2-1-3
+        assert_eq!(held_karp(&distances, Objective::Minimise, false), 4);
+        assert_eq!(held_karp(&distances, Objective::Maximise, false), 12);
+
+        // Best cycles are 0-1-2-3 and 0-2-1-3
+        assert_eq!(held_karp(&distances, Objective::Minimise, true), 7);
+        assert_eq!(held_karp(&distances, Objective::Maximise, true), 14);
+    }
+}
@@ -1,13 +1,14 @@
-use crate::yr2017::day10::{KnotHashResult, knot_hash};
+use crate::knot_hash::knot_hash;
 
 /// The size of the grid (in both dimensions)
 const GRID_SIZE: u8 = 128;
 
 /// Calculates the disk grid from the given input key
-///  The returned vector always contains 128 elements
-fn get_grid(key: &str) -> Vec<KnotHashResult> {
-    (0..128)
-        .map(|row| knot_hash(format!("{}-{}", key, row).bytes()))
+///  The returned grid always contains 128 rows of 128 squares, where each
+///  row is the bits of the knot hash of the key and the row number.
+fn get_grid(key: &str) -> Vec<Vec<bool>> {
+    (0..GRID_SIZE)
+        .map(|row| knot_hash(format!("{}-{}", key, row)).bits().collect())
         .collect()
 }
 
@@ -15,29 +16,23 @@ fn get_grid(key: &str) -> Vec<KnotHashResult> {
 pub fn star1(input: &str) -> String {
     get_grid(input)
         .iter()
-        .map(|row| row.iter().map(|b| b.count_ones()).sum::<u32>())
-        .sum::<u32>()
+        .map(|row| row.iter().filter(|&&used| used).count())
+        .sum::<usize>()
         .to_string()
 }
 
 /// Returns the value at a given point
-fn get_point(grid: &[KnotHashResult], (x, y): (u8, u8)) -> bool {
-    assert!(y < GRID_SIZE);
-    assert!(x < GRID_SIZE);
-
-    (grid[y as usize][(x / 8) as usize] & (1 << (7 - x % 8))) != 0
+fn get_point(grid: &[Vec<bool>], (x, y): (u8, u8)) -> bool {
+    grid[y as usize][x as usize]
 }
 
 /// Clears the value at a given point
-fn clear_point(grid: &mut [KnotHashResult], (x, y): (u8, u8)) {
-    assert!(y < GRID_SIZE);
-    assert!(x < GRID_SIZE);
-
-    grid[y as usize][(x / 8) as usize] &= !(1 << (7 - x % 8));
+fn clear_point(grid: &mut [Vec<bool>], (x, y): (u8, u8)) {
+    grid[y as usize][x as usize] = false;
 }
 
 /// Consumes the region containing the given point
-fn consume_region(grid: &mut [KnotHashResult], point: (u8, u8)) {
+fn consume_region(grid: &mut [Vec<bool>], point: (u8, u8)) {
     // Only continue if point is actually set
     if get_point(grid, point) {
         // Erase point and recurse to 4 surrounding points
@@ -58,21 +53,16 @@ fn consume_region(grid: &mut [KnotHashResult], point: (u8, u8)) {
 }
 
 /// Finds any point in the grid which is in use
-fn find_used_point(grid: &[KnotHashResult]) -> Option<(u8, u8)> {
-    for y in 0..GRID_SIZE {
-        for byte in 0..(GRID_SIZE / 8) {
-            let byte_value = grid[y as usize][byte as usize];
-            if byte_value != 0 {
-                return Some((byte * 8 + byte_value.leading_zeros() as u8, y));
-            }
-        }
-    }
-
-    None
+fn find_used_point(grid: &[Vec<bool>]) -> Option<(u8, u8)> {
+    grid.iter().enumerate().find_map(|(y, row)| {
+        row.iter()
+            .position(|&used| used)
+            .map(|x| (x as u8, y as u8))
+    })
 }
 
 /// Counts the number of regions in a grid
-fn count_regions(mut grid: Vec<KnotHashResult>) -> u32 {
+fn count_regions(mut grid: Vec<Vec<bool>>) -> u32 {
     let mut regions = 0;
     while let Some(point) = find_used_point(&grid) {
         consume_region(&mut grid, point);
@@ -1,5 +1,7 @@
 use itertools::Itertools;
 
+use crate::dihedral::Dihedral;
+
 use std::collections::HashMap;
 use std::ops::{Index, IndexMut};
 use std::str::FromStr;
@@ -54,15 +56,12 @@ impl Image {
         Self::from_generator(width, height, |x, y| self[coord_transform(x, y)])
     }
 
-    /// Like transform, but keeps the same size
-    ///  coord_transform is provided (new x, new y, width, height)
-    fn transform_noscale<F>(&self, coord_transform: F) -> Image
-    where
-        F: Fn(usize, usize, usize, usize) -> (usize, usize),
-    {
-        let height = self.height();
-        self.transform(self.width, height, |x, y| {
-            coord_transform(x, y, self.width, height)
+    /// Applies a rotation or reflection to a square image
+    fn dihedral(&self, transform: Dihedral) -> Image {
+        assert!(self.width == self.height());
+        let inverse = transform.inverse();
+        self.transform(self.width, self.width, |x, y| {
+            inverse.apply((x, y), self.width)
         })
     }
 
@@ -70,6 +69,20 @@ impl Image {
     fn subimage(&self, x: usize, y: usize, width: usize, height: usize) -> Image {
         self.transform(width, height, |newx, newy| (x + newx, y + newy))
     }
+
+    /// Splits a square image into square blocks of the given size in row major order
+    fn blocks(&self, size: usize) -> Vec<Image> {
+        let count = self.width / size;
+        (0..count)
+            .cartesian_product(0..count)
+            .map(|(by, bx)| self.subimage(bx * size, by * size, size, size))
+            .collect()
+    }
+
+    /// Returns the number of set pixels
+    fn lit(&self) -> usize {
+        self.data.iter().filter(|&&p| p).count()
+    }
 }
 
 // Image indexing by x,y coordinates
@@ -137,21 +150,9 @@ fn parse_rules<'a, I: Iterator<Item = &'a str>>(lines: I) -> HashMap<Image, Imag
         let line_parts: Vec<Image> = line.split("=>").map(|s| s.parse().unwrap()).collect();
         assert_eq!(line_parts.len(), 2);
 
-        // Insert all linear transformations of left side into the ruleset
-        //  This is the dihedral group of order 8 (a = rotate 90, b = flip horiz)
-        let lhs_list = vec![
-            line_parts[0].clone(),                                        // e Identity
-            line_parts[0].transform_noscale(|x, y, _, h| (y, h - x - 1)), // a Rotate 90
-            line_parts[0].transform_noscale(|x, y, w, h| (w - x - 1, h - y - 1)), // aa Rotate 180
-            line_parts[0].transform_noscale(|x, y, w, _| (w - y - 1, x)), // aaa Rotate 270
-            line_parts[0].transform_noscale(|x, y, w, _| (w - x - 1, y)), // b Flip horizontal
-            line_parts[0].transform_noscale(|x, y, w, h| (w - y - 1, h - x - 1)), // ab
-            line_parts[0].transform_noscale(|x, y, _, h| (x, h - y - 1)), // aab Flip vertical
-            line_parts[0].transform_noscale(|x, y, _, _| (y, x)),         // aaab
-        ];
-
-        for lhs in lhs_list {
-            rules.insert(lhs, line_parts[1].clone());
+        // Insert all rotations and reflections of left side into the ruleset
+        for transform in Dihedral::all() {
+            rules.insert(line_parts[0].dihedral(transform), line_parts[1].clone());
         }
     }
 
@@ -168,13 +169,10 @@ fn fractal_iterate(image: Image, rules: &HashMap<Image, Image>) -> Image {
     // Get list of blocks which we're going to expand into
     let block_size = if image.width.is_multiple_of(2) { 2 } else { 3 };
     let block_count = image.width / block_size;
-    let matched_blocks: Vec<&Image> = (0..block_count)
-        .cartesian_product(0..block_count)
-        .map(|(by, bx)| {
-            rules
-                .get(&image.subimage(bx * block_size, by * block_size, block_size, block_size))
-                .unwrap()
-        })
+    let matched_blocks: Vec<&Image> = image
+        .blocks(block_size)
+        .iter()
+        .map(|block| rules.get(block).unwrap())
         .collect();
 
     // Splice blocks together
@@ -189,6 +187,34 @@ fn fractal_iterate(image: Image, rules: &HashMap<Image, Image>) -> Image {
     )
 }
 
+/// Counts the set pixels after the given number of iterations
+///  Every three iterations, a 3x3 block expands into a 9x9 image whose nine
+///  3x3 blocks never interact again. So only the number of each type of
+///  3x3 block needs to be tracked, and each type is only expanded once.
+fn count_lit(initial: Image, rules: &HashMap<Image, Image>, iterations: usize) -> u128 {
+    let expand = |block: Image, n| (0..n).fold(block, |prev, _| fractal_iterate(prev, rules));
+
+    let mut children: HashMap<Image, Vec<Image>> = HashMap::new();
+    let mut counts: HashMap<Image, u128> = HashMap::from([(initial, 1)]);
+    for _ in 0..iterations / 3 {
+        let mut next = HashMap::new();
+        for (block, count) in counts {
+            let blocks = children
+                .entry(block)
+                .or_insert_with_key(|block| expand(block.clone(), 3).blocks(3));
+            for child in blocks {
+                *next.entry(child.clone()).or_default() += count;
+            }
+        }
+        counts = next;
+    }
+
+    counts
+        .into_iter()
+        .map(|(block, count)| count * expand(block, iterations % 3).lit() as u128)
+        .sum()
+}
+
 /// Find number of enabled bits after n iterations
 fn star_common(input: &str, default: usize) -> String {
     // Read number of iterations from the first line
@@ -203,12 +229,7 @@ fn star_common(input: &str, default: usize) -> String {
 
     let rules = parse_rules(lines);
     let initial_image: Image = ".#./..#/###".parse().unwrap();
-
-    // Perform fractal iteration 5 times
-    let result = (0..iterations).fold(initial_image, |prev, _| fractal_iterate(prev, &rules));
-
-    // Return number of set bits in the final image
-    result.data.iter().filter(|&p| *p).count().to_string()
+    count_lit(initial_image, &rules, iterations).to_string()
 }
 
 pub fn star1(input: &str) -> String {
@@ -217,3 +238,45 @@ pub fn star1(input: &str) -> String {
 pub fn star2(input: &str) -> String {
     star_common(input, 18)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Builds a complete rule set where each pattern maps to an arbitrary
+    /// image chosen from the smallest of its transforms
+    fn all_rules() -> HashMap<Image, Image> {
+        let mut rules = HashMap::new();
+        for size in [2, 3] {
+            for bits in 0..1u32 << (size * size) {
+                let pattern =
+                    Image::from_generator(size, size, |x, y| bits & (1 << (y * size + x)) != 0);
+                let key = Dihedral::all()
+                    .map(|d| pattern.dihedral(d).data)
+                    .min()
+                    .unwrap();
+                let seed = key.iter().fold(size as u32, |acc, &p| {
+                    acc.wrapping_mul(31).wrapping_add(u32::from(p))
+                });
+                let output = Image::from_generator(size + 1, size + 1, |x, y| {
+                    (seed.wrapping_mul(2_654_435_761) >> (x + y * (size + 1) + 7)) & 1 != 0
+                });
+                rules.insert(pattern, output);
+            }
+        }
+        rules
+    }
+
+    #[test]
+    fn block_counts() {
+        let rules = all_rules();
+        let mut image: Image = ".#./..#/###".parse().unwrap();
+        for n in 0..=10 {
+            assert_eq!(
+                count_lit(".#./..#/###".parse().unwrap(), &rules, n),
+                image.lit() as u128
+            );
+            image = fractal_iterate(image, &rules);
+        }
+    }
+}
@@ -1,8 +1,16 @@
 use std::collections::{HashMap, VecDeque};
-use std::hash::Hash;
+use std::fmt;
 use std::iter;
 use std::str::FromStr;
 
+use crate::options;
+
+/// Number of cells either side of the cursor to print in verbose mode
+const TAPE_WINDOW: usize = 20;
+
+/// Maximum number of steps to run a machine in busy beaver mode
+const BUSY_BEAVER_LIMIT: u64 = 1_000_000_000_000;
+
 /// Direction the tape cursor can move
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
 enum TapeMove {
@@ -23,84 +31,357 @@ impl FromStr for TapeMove {
     }
 }
 
-/// Type used for the transition mapping dictionary
-type TransitionMap<State, Symbol> = HashMap<(State, Symbol), (State, Symbol, TapeMove)>;
+/// A single entry in a state table
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct Transition {
+    write: u8,
+    tape_move: TapeMove,
+    next: usize,
+}
+
+/// The transitions of a turing machine, indexed by state and then symbol
+///  States are numbered from 0, which is always the initial state. Symbol 0
+///  is the blank symbol. A missing transition halts the machine
+///  immediately, while moving to a state which does not exist halts the
+///  machine after the transition has been applied.
+///
+///  The compact text format (used by Display and FromStr) lists each state
+///  separated by underscores. Each state has one transition per symbol
+///  consisting of the symbol to write, L or R, and the next state as a
+///  letter (A is state 0). Missing transitions are written "---". For
+///  example, the 2 state busy beaver is "1RB1LB_1LA1RZ".
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct StateTable {
+    transitions: Vec<Vec<Option<Transition>>>,
+}
+
+impl fmt::Display for StateTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, state) in self.transitions.iter().enumerate() {
+            if i > 0 {
+                write!(f, "_")?;
+            }
+
+            for transition in state {
+                match transition {
+                    Some(t) => {
+                        let tape_move = if t.tape_move == TapeMove::Left {
+                            'L'
+                        } else {
+                            'R'
+                        };
+                        let next = char::from(b'A' + t.next as u8);
+                        write!(f, "{}{tape_move}{next}", t.write)?;
+                    }
+                    None => write!(f, "---")?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for StateTable {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<StateTable, String> {
+        let states: Vec<&[u8]> = s.trim().split('_').map(str::as_bytes).collect();
+        let symbols = states[0].len() / 3;
+        if !(2..=10).contains(&symbols) {
+            return Err(format!("invalid number of symbols in state {s}"));
+        }
+
+        let transitions = states
+            .iter()
+            .map(|state| {
+                if state.len() != symbols * 3 {
+                    return Err("states have different numbers of symbols".to_string());
+                }
+
+                state
+                    .chunks(3)
+                    .map(|t| {
+                        let next = match t[2] {
+                            b'-' => return Ok(None),
+                            b'A'..=b'Z' => usize::from(t[2] - b'A'),
+                            _ => return Err(format!("invalid state {}", char::from(t[2]))),
+                        };
+                        let tape_move = match t[1] {
+                            b'L' => TapeMove::Left,
+                            b'R' => TapeMove::Right,
+                            _ => return Err(format!("invalid move {}", char::from(t[1]))),
+                        };
+                        let write = t[0].wrapping_sub(b'0');
+                        if usize::from(write) >= symbols {
+                            return Err(format!("invalid symbol {}", char::from(t[0])));
+                        }
+
+                        Ok(Some(Transition {
+                            write,
+                            tape_move,
+                            next,
+                        }))
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(StateTable { transitions })
+    }
+}
+
+/// Number of bits in each block of the tape
+const BLOCK_BITS: u32 = 16;
+
+/// Maximum number of steps to run within a single block at once
+const BLOCK_STEP_LIMIT: u64 = 1 << 20;
 
-/// A turing machine with infinte tape in both directions
-struct TuringMachine<State, Symbol> {
-    /// Blank symbol (constant)
-    blank: Symbol,
+/// Returns the number of bits needed to store each symbol on a tape
+fn cell_bits(symbols: usize) -> u32 {
+    (usize::BITS - (symbols - 1).leading_zeros()).max(1)
+}
 
-    /// Map of state transitions (constant)
-    transitions: TransitionMap<State, Symbol>,
+/// Reads a cell from a block
+fn get_cell(block: u16, cell_bits: u32, cell: i8) -> u8 {
+    ((block >> (cell as u32 * cell_bits)) & ((1 << cell_bits) - 1)) as u8
+}
 
-    /// Current state
-    state: State,
+/// Writes a cell in a block
+fn set_cell(block: u16, cell_bits: u32, cell: i8, symbol: u8) -> u16 {
+    let shift = cell as u32 * cell_bits;
+    let mask = ((1 << cell_bits) - 1) << shift;
+    (block & !mask) | (u16::from(symbol) << shift)
+}
 
-    /// Current cursor position
-    ///  The position must be within the tape size or one off the end
-    cursor: usize,
+/// A tape divided into fixed size blocks which is blank beyond both ends
+///  Each block packs as many cells as fit into BLOCK_BITS bits, with the
+///  leftmost cell in the least significant bits.
+#[derive(Debug, Clone)]
+struct Tape {
+    blocks: VecDeque<u16>,
+    cell_bits: u32,
+    /// Index of the block containing position 0
+    origin: usize,
 
-    /// Tape contents
-    tape: VecDeque<Symbol>,
+    /// Block and cell within that block containing the cursor
+    block: usize,
+    cell: i8,
+    /// Range of positions the cursor has visited
+    visited: (i64, i64),
 }
 
-impl<State: Copy + Eq + Hash, Symbol: Copy + Eq + Hash> TuringMachine<State, Symbol> {
-    /// Creates a new turing machine
-    fn new(
-        blank: Symbol,
-        transitions: TransitionMap<State, Symbol>,
-        initial_state: State,
-        mut initial_tape: Vec<Symbol>,
-    ) -> Self {
-        assert!(!initial_tape.contains(&blank));
+impl Tape {
+    /// Creates a blank tape for the given number of symbols
+    fn new(symbols: usize) -> Self {
+        Tape {
+            blocks: VecDeque::from([0]),
+            cell_bits: cell_bits(symbols),
+            origin: 0,
+            block: 0,
+            cell: 0,
+            visited: (0, 0),
+        }
+    }
+
+    /// Returns the number of cells in each block
+    fn block_cells(&self) -> i8 {
+        (BLOCK_BITS / self.cell_bits) as i8
+    }
+
+    /// Returns the position of the first cell of the cursor's block
+    fn block_start(&self) -> i64 {
+        (self.block as i64 - self.origin as i64) * i64::from(self.block_cells())
+    }
+
+    /// Returns the symbol under the cursor
+    fn head(&self) -> u8 {
+        get_cell(self.blocks[self.block], self.cell_bits, self.cell)
+    }
+
+    /// Returns the symbol at a position
+    fn get(&self, position: i64) -> u8 {
+        let cells = i64::from(self.block_cells());
+        let block = position.div_euclid(cells) + self.origin as i64;
+        usize::try_from(block)
+            .ok()
+            .and_then(|b| self.blocks.get(b))
+            .map_or(0, |&b| {
+                get_cell(b, self.cell_bits, position.rem_euclid(cells) as i8)
+            })
+    }
+
+    /// Replaces the cursor's block with the result of running the machine on it
+    fn apply(&mut self, run: &BlockRun) {
+        let start = self.block_start();
+        self.blocks[self.block] = run.block;
+        self.visited = (
+            self.visited.0.min(start + i64::from(run.visited.0)),
+            self.visited.1.max(start + i64::from(run.visited.1)),
+        );
+
+        self.cell = run.cell;
+        if self.cell < 0 {
+            if self.block == 0 {
+                self.blocks.push_front(0);
+                self.origin += 1;
+            } else {
+                self.block -= 1;
+            }
+            self.cell = self.block_cells() - 1;
+        } else if self.cell >= self.block_cells() {
+            self.block += 1;
+            if self.block == self.blocks.len() {
+                self.blocks.push_back(0);
+            }
+            self.cell = 0;
+        }
+    }
 
-        // Cursor must point to a valid tape position
-        if initial_tape.is_empty() {
-            initial_tape.push(blank);
+    /// Returns the number of non-blank symbols on the tape
+    fn non_blank(&self) -> u64 {
+        self.blocks
+            .iter()
+            .flat_map(|&b| (0..self.block_cells()).map(move |c| (b, c)))
+            .filter(|&(b, c)| get_cell(b, self.cell_bits, c) != 0)
+            .count() as u64
+    }
+
+    /// Returns the symbols within radius cells of the cursor, with the cursor in brackets
+    fn window(&self, radius: usize) -> String {
+        let position = self.block_start() + i64::from(self.cell);
+        let radius = radius as i64;
+        let join = |range: std::ops::Range<i64>| {
+            range
+                .map(|p| self.get(p).to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
         };
 
+        format!(
+            "{} [{}] {}",
+            join(position - radius..position),
+            self.head(),
+            join(position + 1..position + radius + 1)
+        )
+    }
+}
+
+/// The result of running a machine within a single block
+#[derive(Debug, Clone, Copy)]
+struct BlockRun {
+    state: u8,
+    block: u16,
+    /// Final cursor cell, which is -1 or the number of cells in a block if
+    /// the cursor left the block
+    cell: i8,
+    /// Range of cells visited, including a cell the cursor moved to outside the block
+    visited: (i8, i8),
+    steps: u32,
+}
+
+/// A turing machine running on a block tape
+///  Runs entering a block at either end are cached, so the machine can
+///  skip across a whole block at once after it has seen it before.
+struct TuringMachine<'a> {
+    table: &'a StateTable,
+    state: usize,
+    tape: Tape,
+    steps: u64,
+
+    /// Cached runs indexed by state, block and which end the cursor starts at
+    cache: Vec<Option<BlockRun>>,
+}
+
+impl<'a> TuringMachine<'a> {
+    /// Creates a new turing machine with a blank tape
+    fn new(table: &'a StateTable) -> Self {
+        let states = table.transitions.len();
         TuringMachine {
-            blank,
-            transitions,
-            state: initial_state,
-            cursor: 0,
-            tape: initial_tape.into(),
+            table,
+            state: 0,
+            tape: Tape::new(table.transitions[0].len()),
+            steps: 0,
+            cache: vec![None; states << (BLOCK_BITS + 1)],
         }
     }
 
-    /// Executes one step of the turing machine
-    ///  Returns true if the machine is still running
-    fn step(&mut self) -> bool {
-        if let Some(&(new_state, new_symbol, tape_move)) =
-            self.transitions.get(&(self.state, self.tape[self.cursor]))
+    /// Returns the transition for a state and symbol, or None to halt
+    fn transition(&self, state: usize, symbol: u8) -> Option<Transition> {
+        self.table
+            .transitions
+            .get(state)
+            .and_then(|s| s[usize::from(symbol)])
+    }
+
+    /// Runs the machine on the cursor's block until the cursor leaves the
+    /// block, the machine halts, or it has run the given number of steps
+    fn run_block(&self, max_steps: u64) -> BlockRun {
+        let cell_bits = self.tape.cell_bits;
+        let cells = self.tape.block_cells();
+        let mut state = self.state;
+        let mut run = BlockRun {
+            state: 0,
+            block: self.tape.blocks[self.tape.block],
+            cell: self.tape.cell,
+            visited: (self.tape.cell, self.tape.cell),
+            steps: 0,
+        };
+
+        while u64::from(run.steps) < max_steps
+            && (0..cells).contains(&run.cell)
+            && let Some(t) = self.transition(state, get_cell(run.block, cell_bits, run.cell))
         {
-            self.state = new_state;
-            self.tape[self.cursor] = new_symbol;
-            if tape_move == TapeMove::Left {
-                if self.cursor == 0 {
-                    // Move the tape instead of the cursor when at position 0
-                    self.tape.push_front(self.blank);
-                } else {
-                    self.cursor -= 1;
+            run.block = set_cell(run.block, cell_bits, run.cell, t.write);
+            run.cell += match t.tape_move {
+                TapeMove::Left => -1,
+                TapeMove::Right => 1,
+            };
+            run.visited = (run.visited.0.min(run.cell), run.visited.1.max(run.cell));
+            run.steps += 1;
+            state = t.next;
+        }
+
+        run.state = state as u8;
+        run
+    }
+
+    /// Runs the machine until it halts or has run the given number of steps
+    ///  Returns true if the machine halted.
+    fn run(&mut self, max_steps: u64) -> bool {
+        let last_cell = self.tape.block_cells() - 1;
+        while self.steps < max_steps {
+            if self.transition(self.state, self.tape.head()).is_none() {
+                return true;
+            }
+
+            let budget = max_steps - self.steps;
+            let cached = if self.tape.cell == 0 || self.tape.cell == last_cell {
+                let index = (((self.state << BLOCK_BITS)
+                    | usize::from(self.tape.blocks[self.tape.block]))
+                    << 1)
+                    | usize::from(self.tape.cell != 0);
+                if self.cache[index].is_none() {
+                    self.cache[index] = Some(self.run_block(BLOCK_STEP_LIMIT));
                 }
+                self.cache[index].filter(|run| u64::from(run.steps) <= budget)
             } else {
-                self.cursor += 1;
-                if self.cursor == self.tape.len() {
-                    self.tape.push_back(self.blank);
-                }
-            }
+                None
+            };
 
-            true
-        } else {
-            false
+            let run = cached.unwrap_or_else(|| self.run_block(budget.min(BLOCK_STEP_LIMIT)));
+            self.tape.apply(&run);
+            self.state = usize::from(run.state);
+            self.steps += u64::from(run.steps);
         }
+
+        false
     }
 }
 
 /// Parses a "day 25" turing machine
-///  Returns the turing machine and the number of steps to run
-fn parse_machine(input: &str) -> (TuringMachine<char, bool>, usize) {
+///  Returns the state table and the number of steps to run
+fn parse_machine(input: &str) -> (StateTable, u64) {
     /// Removes given prefix and returns the second part
     fn trim_prefix<'a>(s: &'a str, prefix: &'_ str) -> Option<&'a str> {
         if s.starts_with(prefix) {
@@ -120,20 +401,20 @@ fn parse_machine(input: &str) -> (TuringMachine<char, bool>, usize) {
     }
 
     /// Parses a symbol string
-    fn parse_symbol(s: &str) -> bool {
+    fn parse_symbol(s: &str) -> u8 {
         if s == "0" {
-            false
+            0
         } else if s == "1" {
-            true
+            1
         } else {
             panic!("invalid symbol: {}", s)
         }
     }
 
     let mut initial_state = None;
-    let mut checksum = usize::MAX;
+    let mut checksum = u64::MAX;
 
-    let mut transitions: TransitionMap<char, bool> = HashMap::new();
+    let mut transitions: HashMap<(char, u8), (char, u8, TapeMove)> = HashMap::new();
     let mut current_state = None;
     let mut current_symbol = None;
     let mut next_symbol = None;
@@ -187,21 +468,166 @@ fn parse_machine(input: &str) -> (TuringMachine<char, bool>, usize) {
         }
     }
 
-    (
-        TuringMachine::new(false, transitions, initial_state.unwrap(), vec![]),
-        checksum,
-    )
+    // Number the states with the initial state first
+    let initial_state = initial_state.unwrap();
+    let mut states: Vec<char> = transitions.keys().map(|&(s, _)| s).collect();
+    states.sort_by_key(|&s| (s != initial_state, s));
+    states.dedup();
+    let index = |s: char| states.iter().position(|&t| t == s).unwrap_or(states.len());
+
+    let table = StateTable {
+        transitions: states
+            .iter()
+            .map(|&state| {
+                (0..2)
+                    .map(|symbol| {
+                        transitions
+                            .get(&(state, symbol))
+                            .map(|&(next, write, tape_move)| Transition {
+                                write,
+                                tape_move,
+                                next: index(next),
+                            })
+                    })
+                    .collect()
+            })
+            .collect(),
+    };
+
+    (table, checksum)
+}
+
+/// Prints the tape around the cursor in verbose mode
+fn print_tape(machine: &TuringMachine) {
+    if options::verbose() {
+        eprintln!("{}", machine.tape.window(TAPE_WINDOW));
+    }
 }
 
 /// Calculate diagnostic checksum
+///  The input can also be a state table in the compact format, optionally
+///  followed by the number of steps on the next line. Without a number of
+///  steps, the machine is run as a busy beaver until it halts and the step
+///  count, number of non-blank symbols and number of cells visited are
+///  returned.
 pub fn star1(input: &str) -> String {
-    let (mut machine, steps) = parse_machine(input);
-    for _ in 0..steps {
-        if !machine.step() {
+    let (table, steps) = if input.trim_start().starts_with("Begin") {
+        let (table, steps) = parse_machine(input);
+        (table, Some(steps))
+    } else {
+        let mut lines = input.lines();
+        let table = lines.next().unwrap().parse().unwrap();
+        (table, lines.next().map(|l| l.trim().parse().unwrap()))
+    };
+
+    let mut machine = TuringMachine::new(&table);
+    if let Some(steps) = steps {
+        if machine.run(steps) {
             panic!("machine stopped ?!");
         }
+
+        print_tape(&machine);
+        machine.tape.non_blank().to_string()
+    } else {
+        if !machine.run(BUSY_BEAVER_LIMIT) {
+            panic!("machine did not halt after {BUSY_BEAVER_LIMIT} steps");
+        }
+
+        print_tape(&machine);
+        let (min, max) = machine.tape.visited;
+        format!(
+            "{} steps, {} non-blank, {} cells",
+            machine.steps,
+            machine.tape.non_blank(),
+            max - min + 1
+        )
     }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "Begin in state A.
+Perform a diagnostic checksum after 6 steps.
+
+In state A:
+  If the current value is 0:
+    - Write the value 1.
+    - Move one slot to the right.
+    - Continue with state B.
+  If the current value is 1:
+    - Write the value 0.
+    - Move one slot to the left.
+    - Continue with state B.
 
-    // Count ones
-    machine.tape.iter().filter(|&v| *v).count().to_string()
+In state B:
+  If the current value is 0:
+    - Write the value 1.
+    - Move one slot to the left.
+    - Continue with state A.
+  If the current value is 1:
+    - Write the value 1.
+    - Move one slot to the right.
+    - Continue with state A.";
+
+    #[test]
+    fn compact_format() {
+        let (table, steps) = parse_machine(EXAMPLE);
+        assert_eq!(steps, 6);
+        assert_eq!(table.to_string(), "1RB0LB_1LA1RA");
+        assert_eq!("1RB0LB_1LA1RA".parse(), Ok(table));
+
+        let halting: StateTable = "1RB1LB_1LA---".parse().unwrap();
+        assert_eq!(halting.to_string(), "1RB1LB_1LA---");
+        assert_eq!(halting.transitions[1][1], None);
+
+        let halting: StateTable = "1RB1LB_1LA1RZ".parse().unwrap();
+        assert_eq!(halting.to_string(), "1RB1LB_1LA1RZ");
+        assert!("1RB1LB_1LA".parse::<StateTable>().is_err());
+        assert!("1RB2LB_1LA1RA".parse::<StateTable>().is_err());
+    }
+
+    #[test]
+    fn busy_beavers() {
+        assert_eq!(star1("1RB1LB_1LA1RZ"), "6 steps, 4 non-blank, 4 cells");
+        assert_eq!(
+            star1("1RB1RZ_1LB0RC_1LC1LA"),
+            "21 steps, 5 non-blank, 5 cells"
+        );
+        assert_eq!(
+            star1("1RB1LB_1LA0LC_1RZ1LD_1RD0RA"),
+            "107 steps, 13 non-blank, 14 cells"
+        );
+        assert_eq!(
+            star1("1RB1LC_1RC1RB_1RD0LE_1LA1LD_1RZ0LA"),
+            "47176870 steps, 4098 non-blank, 12289 cells"
+        );
+        assert_eq!(
+            star1("1RB2LB1RZ_2LA2RB1LB"),
+            "38 steps, 9 non-blank, 9 cells"
+        );
+    }
+
+    #[test]
+    fn partial_runs() {
+        // Stopping in the middle of blocks must give the same tape
+        let table: StateTable = "1RB0LC_1LA1RD_1RA0LE_1RA0RB_1LF1LC_1RD1RA".parse().unwrap();
+        let mut whole = TuringMachine::new(&table);
+        let mut parts = TuringMachine::new(&table);
+        assert!(!whole.run(100_000));
+        for steps in (7..=100_000).step_by(7).chain(iter::once(100_000)) {
+            assert!(!parts.run(steps));
+        }
+        assert_eq!(parts.tape.non_blank(), whole.tape.non_blank());
+        assert_eq!(parts.tape.window(40), whole.tape.window(40));
+    }
+
+    #[test]
+    fn window() {
+        let (table, _) = parse_machine(EXAMPLE);
+        let mut machine = TuringMachine::new(&table);
+        assert!(!machine.run(6));
+        assert_eq!(machine.tape.window(3), "0 1 1 [0] 1 0 0");
+    }
 }
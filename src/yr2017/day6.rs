@@ -10,13 +10,10 @@ where
     match iter.next() {
         Some(value) => {
             let mut max = (value, 0);
-            let mut index = 1;
-            for value in iter {
+            for (index, value) in (1..).zip(iter) {
                 if value > max.0 {
                     max = (value, index);
                 }
-
-                index += 1;
             }
 
             Some(max.1)
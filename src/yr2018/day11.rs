@@ -1,7 +1,61 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use nalgebra::DMatrix;
+
+const GRID_SIZE: usize = 300;
+
+/// Calculates the power level of a single fuel cell
+fn power_level(serial: i32, x: i32, y: i32) -> i32 {
+    let rack_id = x + 10;
+    ((rack_id * y + serial) * rack_id / 100) % 10 - 5
+}
+
+/// Builds the summed-area table of the fuel cell grid
+///  Entry (x, y) contains the sum of all cells with coordinates less than (x, y)
+fn summed_area_table(serial: i32) -> DMatrix<i32> {
+    let mut table = DMatrix::zeros(GRID_SIZE + 1, GRID_SIZE + 1);
+    for x in 1..=GRID_SIZE {
+        for y in 1..=GRID_SIZE {
+            table[(x, y)] =
+                power_level(serial, x as i32, y as i32) + table[(x - 1, y)] + table[(x, y - 1)]
+                    - table[(x - 1, y - 1)];
+        }
+    }
+    table
+}
+
+/// Returns the total power of the square with top-left (x, y) and the given size
+fn square_power(table: &DMatrix<i32>, x: usize, y: usize, size: usize) -> i32 {
+    table[(x + size - 1, y + size - 1)]
+        - table[(x - 1, y + size - 1)]
+        - table[(x + size - 1, y - 1)]
+        + table[(x - 1, y - 1)]
+}
+
+/// Finds the most powerful square with the given size
+///  Returns (power, x, y)
+fn best_square(table: &DMatrix<i32>, size: usize) -> (i32, usize, usize) {
+    (1..=GRID_SIZE + 1 - size)
+        .flat_map(|x| (1..=GRID_SIZE + 1 - size).map(move |y| (x, y)))
+        .map(|(x, y)| (square_power(table, x, y, size), x, y))
+        .max_by_key(|&(power, x, y)| (power, -(x as i64), -(y as i64)))
+        .unwrap()
+}
+
+/// Find the most powerful 3x3 square of fuel cells
+pub fn star1(input: &str) -> String {
+    let table = summed_area_table(input.trim().parse().unwrap());
+    let (_, x, y) = best_square(&table, 3);
+    format!("{x},{y}")
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Find the most powerful square of fuel cells of any size
+pub fn star2(input: &str) -> String {
+    let table = summed_area_table(input.trim().parse().unwrap());
+    let (_, x, y, size) = (1..=GRID_SIZE)
+        .map(|size| {
+            let (power, x, y) = best_square(&table, size);
+            (power, x, y, size)
+        })
+        .max_by_key(|&(power, _, _, size)| (power, -(size as i64)))
+        .unwrap();
+    format!("{x},{y},{size}")
 }
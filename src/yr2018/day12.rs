@@ -1,7 +1,100 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use std::collections::HashSet;
+
+/// A row of pots, storing the position of the first pot and the pot states
+#[derive(Clone, Debug)]
+struct Pots {
+    offset: i64,
+    pots: Vec<bool>,
+}
+
+impl Pots {
+    /// Removes empty pots from each end of the row
+    fn trim(mut self) -> Pots {
+        let first = self.pots.iter().position(|&p| p).unwrap_or(0);
+        let last = self.pots.iter().rposition(|&p| p).map_or(0, |l| l + 1);
+        self.offset += first as i64;
+        self.pots = self.pots[first..last.max(first)].to_vec();
+        self
+    }
+
+    /// Advances the row by one generation
+    fn step(&self, rules: &HashSet<u8>) -> Pots {
+        let mut pattern = 0u8;
+        let mut pots = Vec::with_capacity(self.pots.len() + 4);
+        for &pot in self.pots.iter().chain([false; 4].iter()) {
+            pattern = ((pattern << 1) | u8::from(pot)) & 0x1F;
+            pots.push(rules.contains(&pattern));
+        }
+
+        Pots {
+            offset: self.offset - 2,
+            pots,
+        }
+        .trim()
+    }
+
+    /// Returns the sum of the numbers of all pots containing plants
+    fn sum(&self) -> i64 {
+        (self.offset..)
+            .zip(&self.pots)
+            .filter(|&(_, &p)| p)
+            .map(|(i, _)| i)
+            .sum()
+    }
+}
+
+/// Parses a pattern of pots
+fn parse_pots(s: &str) -> Vec<bool> {
+    s.trim().chars().map(|c| c == '#').collect()
+}
+
+/// Parses the input into the initial state and the set of patterns which produce a plant
+fn parse_input(input: &str) -> (Pots, HashSet<u8>) {
+    let mut lines = input.lines();
+    let initial = Pots {
+        offset: 0,
+        pots: parse_pots(lines.next().unwrap().trim_start_matches("initial state:")),
+    };
+
+    let rules = lines
+        .filter_map(|line| line.split_once("=>"))
+        .filter(|(_, result)| result.trim() == "#")
+        .map(|(pattern, _)| {
+            parse_pots(pattern)
+                .into_iter()
+                .fold(0, |acc, p| (acc << 1) | u8::from(p))
+        })
+        .collect();
+
+    (initial.trim(), rules)
+}
+
+/// Returns the sum of the plant pots after the given number of generations
+///  Once the pattern of pots starts drifting without changing shape, the
+///  remaining generations are skipped.
+fn simulate(input: &str, generations: u64) -> i64 {
+    let (mut pots, rules) = parse_input(input);
+    for generation in 0..generations {
+        let next = pots.step(&rules);
+        if next.pots == pots.pots {
+            let remaining = (generations - generation) as i64;
+            let drift = next.offset - pots.offset;
+            let count = pots.pots.iter().filter(|&&p| p).count() as i64;
+            return pots.sum() + drift * remaining * count;
+        }
+
+        pots = next;
+    }
+
+    pots.sum()
+}
+
+/// Sum of pots containing plants after 20 generations
+pub fn star1(input: &str) -> String {
+    simulate(input, 20).to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Sum of pots containing plants after 50 billion generations
+pub fn star2(input: &str) -> String {
+    simulate(input, 50_000_000_000).to_string()
 }
@@ -1,7 +1,112 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use crate::direction::Direction;
+use nalgebra::Vector2;
+
+/// A mine cart
+#[derive(Clone, Copy, Debug)]
+struct Cart {
+    pos: Vector2<i32>,
+    dir: Direction,
+    turns: u8,
+    crashed: bool,
+}
+
+impl Cart {
+    /// Moves the cart one step along the tracks
+    fn advance(&mut self, tracks: &[Vec<u8>]) {
+        self.pos += self.dir.to_vec_neg();
+        self.dir = match (tracks[self.pos.y as usize][self.pos.x as usize], self.dir) {
+            (b'/', Direction::North | Direction::South) => self.dir.clockwise(),
+            (b'/', _) => self.dir.anticlockwise(),
+            (b'\\', Direction::North | Direction::South) => self.dir.anticlockwise(),
+            (b'\\', _) => self.dir.clockwise(),
+            (b'+', _) => {
+                self.turns = (self.turns + 1) % 3;
+                match self.turns {
+                    1 => self.dir.anticlockwise(),
+                    2 => self.dir,
+                    _ => self.dir.clockwise(),
+                }
+            }
+            _ => self.dir,
+        };
+    }
+}
+
+/// Parses the tracks and returns the track grid and list of carts
+fn parse_input(input: &str) -> (Vec<Vec<u8>>, Vec<Cart>) {
+    let mut carts = Vec::new();
+    let tracks = input
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            line.bytes()
+                .enumerate()
+                .map(|(x, b)| {
+                    let (dir, track) = match b {
+                        b'^' => (Direction::North, b'|'),
+                        b'>' => (Direction::East, b'-'),
+                        b'v' => (Direction::South, b'|'),
+                        b'<' => (Direction::West, b'-'),
+                        _ => return b,
+                    };
+
+                    carts.push(Cart {
+                        pos: Vector2::new(x as i32, y as i32),
+                        dir,
+                        turns: 0,
+                        crashed: false,
+                    });
+                    track
+                })
+                .collect()
+        })
+        .collect();
+
+    (tracks, carts)
+}
+
+/// Runs the carts around the tracks
+///  If stop_on_crash is true, returns the location of the first crash.
+///  Otherwise returns the location of the last remaining cart.
+fn simulate(input: &str, stop_on_crash: bool) -> Vector2<i32> {
+    let (tracks, mut carts) = parse_input(input);
+
+    while carts.len() > 1 {
+        carts.sort_by_key(|c| (c.pos.y, c.pos.x));
+
+        for i in 0..carts.len() {
+            if carts[i].crashed {
+                continue;
+            }
+
+            carts[i].advance(&tracks);
+            let pos = carts[i].pos;
+            if let Some(j) =
+                (0..carts.len()).find(|&j| j != i && !carts[j].crashed && carts[j].pos == pos)
+            {
+                if stop_on_crash {
+                    return pos;
+                }
+
+                carts[i].crashed = true;
+                carts[j].crashed = true;
+            }
+        }
+
+        carts.retain(|c| !c.crashed);
+    }
+
+    carts[0].pos
+}
+
+/// Location of the first crash
+pub fn star1(input: &str) -> String {
+    let pos = simulate(input, true);
+    format!("{},{}", pos.x, pos.y)
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Location of the last remaining cart
+pub fn star2(input: &str) -> String {
+    let pos = simulate(input, false);
+    format!("{},{}", pos.x, pos.y)
 }
@@ -1,7 +1,59 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+/// The recipe scoreboard and the positions of the two elves
+struct Scoreboard {
+    scores: Vec<u8>,
+    elves: [usize; 2],
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+impl Scoreboard {
+    /// Creates a new scoreboard containing the two initial recipes
+    fn new() -> Scoreboard {
+        Scoreboard {
+            scores: vec![3, 7],
+            elves: [0, 1],
+        }
+    }
+
+    /// Creates new recipes and moves the elves
+    fn step(&mut self) {
+        let sum = self.scores[self.elves[0]] + self.scores[self.elves[1]];
+        if sum >= 10 {
+            self.scores.push(sum / 10);
+        }
+        self.scores.push(sum % 10);
+
+        for elf in &mut self.elves {
+            *elf = (*elf + 1 + usize::from(self.scores[*elf])) % self.scores.len();
+        }
+    }
+}
+
+/// Scores of the ten recipes after the given number of recipes
+pub fn star1(input: &str) -> String {
+    let count: usize = input.trim().parse().unwrap();
+    let mut board = Scoreboard::new();
+    while board.scores.len() < count + 10 {
+        board.step();
+    }
+
+    board.scores[count..count + 10]
+        .iter()
+        .map(|s| char::from(b'0' + s))
+        .collect()
+}
+
+/// Number of recipes before the input sequence first appears
+pub fn star2(input: &str) -> String {
+    let target: Vec<u8> = input.trim().bytes().map(|b| b - b'0').collect();
+    let mut board = Scoreboard::new();
+    let mut checked = 0;
+
+    loop {
+        board.step();
+        while checked + target.len() <= board.scores.len() {
+            if board.scores[checked..].starts_with(&target) {
+                return checked.to_string();
+            }
+            checked += 1;
+        }
+    }
 }
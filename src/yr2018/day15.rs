@@ -1,7 +1,153 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use std::collections::VecDeque;
+
+/// A position on the map as (y, x) so that positions sort in reading order
+type Pos = (usize, usize);
+
+/// A goblin or an elf
+#[derive(Clone, Copy, Debug)]
+struct Unit {
+    pos: Pos,
+    hp: i32,
+    elf: bool,
+}
+
+/// Returns the four neighbours of a position in reading order
+fn neighbours((y, x): Pos) -> [Pos; 4] {
+    [(y - 1, x), (y, x - 1), (y, x + 1), (y + 1, x)]
+}
+
+/// Parses the input into a wall map and the list of units
+fn parse_input(input: &str) -> (Vec<Vec<bool>>, Vec<Unit>) {
+    let mut units = Vec::new();
+    let walls = input
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            line.bytes()
+                .enumerate()
+                .map(|(x, b)| {
+                    if b == b'E' || b == b'G' {
+                        units.push(Unit {
+                            pos: (y, x),
+                            hp: 200,
+                            elf: b == b'E',
+                        });
+                    }
+                    b == b'#'
+                })
+                .collect()
+        })
+        .collect();
+
+    (walls, units)
+}
+
+/// Calculates the distances of all reachable squares from start
+fn distances(walls: &[Vec<bool>], units: &[Unit], start: Pos) -> Vec<Vec<Option<usize>>> {
+    let mut dist = vec![vec![None; walls[0].len()]; walls.len()];
+    let mut queue = VecDeque::from([start]);
+    dist[start.0][start.1] = Some(0);
+
+    while let Some(pos) = queue.pop_front() {
+        let next_dist = dist[pos.0][pos.1].map(|d| d + 1);
+        for next in neighbours(pos) {
+            if !walls[next.0][next.1]
+                && dist[next.0][next.1].is_none()
+                && !units.iter().any(|u| u.hp > 0 && u.pos == next)
+            {
+                dist[next.0][next.1] = next_dist;
+                queue.push_back(next);
+            }
+        }
+    }
+
+    dist
+}
+
+/// Chooses the square a unit should move to (or None to stay still)
+fn choose_move(walls: &[Vec<bool>], units: &[Unit], unit: &Unit) -> Option<Pos> {
+    let enemies = units.iter().filter(|u| u.hp > 0 && u.elf != unit.elf);
+    let in_range: Vec<Pos> = enemies.flat_map(|e| neighbours(e.pos)).collect();
+    if in_range.contains(&unit.pos) {
+        return None;
+    }
+
+    // Find nearest reachable square in range of an enemy
+    let from_unit = distances(walls, units, unit.pos);
+    let target = in_range
+        .into_iter()
+        .filter_map(|p| from_unit[p.0][p.1].map(|d| (d, p)))
+        .min()?
+        .1;
+
+    // Take the first step along a shortest path to it
+    let from_target = distances(walls, units, target);
+    neighbours(unit.pos)
+        .into_iter()
+        .filter_map(|p| from_target[p.0][p.1].map(|d| (d, p)))
+        .min()
+        .map(|(_, p)| p)
+}
+
+/// Simulates a battle and returns the outcome
+///  Returns None if must_win is set and an elf dies.
+fn battle(
+    walls: &[Vec<bool>],
+    mut units: Vec<Unit>,
+    elf_power: i32,
+    must_win: bool,
+) -> Option<i32> {
+    for round in 0.. {
+        units.retain(|u| u.hp > 0);
+        units.sort_by_key(|u| u.pos);
+
+        for i in 0..units.len() {
+            if units[i].hp <= 0 {
+                continue;
+            }
+
+            if !units.iter().any(|u| u.hp > 0 && u.elf != units[i].elf) {
+                let hp_sum: i32 = units.iter().filter(|u| u.hp > 0).map(|u| u.hp).sum();
+                return Some(round * hp_sum);
+            }
+
+            if let Some(pos) = choose_move(walls, &units, &units[i]) {
+                units[i].pos = pos;
+            }
+
+            // Attack the weakest adjacent enemy
+            let adjacent = neighbours(units[i].pos);
+            let power = if units[i].elf { elf_power } else { 3 };
+            if let Some(j) = (0..units.len())
+                .filter(|&j| {
+                    units[j].hp > 0
+                        && units[j].elf != units[i].elf
+                        && adjacent.contains(&units[j].pos)
+                })
+                .min_by_key(|&j| (units[j].hp, units[j].pos))
+            {
+                units[j].hp -= power;
+                if must_win && units[j].elf && units[j].hp <= 0 {
+                    return None;
+                }
+            }
+        }
+    }
+
+    unreachable!()
+}
+
+/// Outcome of the battle
+pub fn star1(input: &str) -> String {
+    let (walls, units) = parse_input(input);
+    battle(&walls, units, 3, false).unwrap().to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Outcome of the battle where the elves have just enough power to all survive
+pub fn star2(input: &str) -> String {
+    let (walls, units) = parse_input(input);
+    (4..)
+        .find_map(|power| battle(&walls, units.clone(), power, true))
+        .unwrap()
+        .to_string()
 }
@@ -1,7 +1,101 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use super::device::Opcode;
+use std::collections::HashSet;
+use strum::IntoEnumIterator;
+
+/// The registers of the device in this puzzle
+type Registers = [usize; 4];
+
+/// An instruction sample, containing registers before and after executing it
+struct Sample {
+    before: Registers,
+    instruction: [usize; 4],
+    after: Registers,
+}
+
+impl Sample {
+    /// Returns true if the given opcode is consistent with this sample
+    fn matches(&self, opcode: Opcode) -> bool {
+        let mut regs = self.before;
+        let [_, a, b, c] = self.instruction;
+        opcode.execute(&mut regs, a, b, c).is_some() && regs == self.after
+    }
+}
+
+/// Parses a list of numbers separated by non-digits
+fn parse_numbers(s: &str) -> Vec<usize> {
+    s.split(|c: char| !c.is_ascii_digit())
+        .filter(|n| !n.is_empty())
+        .map(|n| n.parse().unwrap())
+        .collect()
+}
+
+/// Parses a register list
+fn parse_registers(s: &str) -> Registers {
+    parse_numbers(s).try_into().unwrap()
+}
+
+/// Parses the input into a list of samples and the test program
+fn parse_input(input: &str) -> (Vec<Sample>, Vec<[usize; 4]>) {
+    let (samples, program) = input.split_once("\n\n\n").unwrap_or((input, ""));
+    let samples = samples
+        .split("\n\n")
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| {
+            let lines: Vec<&str> = s.trim().lines().collect();
+            Sample {
+                before: parse_registers(lines[0]),
+                instruction: parse_numbers(lines[1]).try_into().unwrap(),
+                after: parse_registers(lines[2]),
+            }
+        })
+        .collect();
+
+    let program = program
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| parse_numbers(l).try_into().unwrap())
+        .collect();
+
+    (samples, program)
+}
+
+/// Number of samples behaving like three or more opcodes
+pub fn star1(input: &str) -> String {
+    parse_input(input)
+        .0
+        .iter()
+        .filter(|s| Opcode::iter().filter(|&op| s.matches(op)).count() >= 3)
+        .count()
+        .to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Deduce the opcode numbers and run the test program
+pub fn star2(input: &str) -> String {
+    let (samples, program) = parse_input(input);
+
+    // Find the possible opcodes for each number
+    let mut possible: Vec<HashSet<Opcode>> = vec![Opcode::iter().collect(); 16];
+    for sample in &samples {
+        possible[sample.instruction[0]].retain(|&op| sample.matches(op));
+    }
+
+    // Eliminate opcodes which are already known
+    let mut opcodes = [None; 16];
+    while let Some(number) = possible.iter().position(|p| p.len() == 1) {
+        let opcode = *possible[number].iter().next().unwrap();
+        opcodes[number] = Some(opcode);
+        for p in &mut possible {
+            p.remove(&opcode);
+        }
+    }
+
+    let mut regs: Registers = [0; 4];
+    for [number, a, b, c] in program {
+        opcodes[number]
+            .expect("ambiguous opcode")
+            .execute(&mut regs, a, b, c)
+            .unwrap();
+    }
+
+    regs[0].to_string()
 }
@@ -1,7 +1,129 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+/// The ground scan, with x coordinates offset so that they start at 0
+struct Ground {
+    cells: Vec<Vec<u8>>,
+    min_y: usize,
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+impl Ground {
+    /// Returns true if the cell at (x, y) can support water above it
+    fn supports(&self, x: usize, y: usize) -> bool {
+        matches!(self.cells[y][x], b'#' | b'~')
+    }
+
+    /// Spreads water sideways from (x, y) in the direction dx
+    ///  Returns the last wet cell and whether it is bounded by clay
+    fn spread(&self, mut x: usize, y: usize, dx: isize) -> (usize, bool) {
+        loop {
+            if !self.supports(x, y + 1) {
+                return (x, false);
+            }
+
+            let next = x.wrapping_add_signed(dx);
+            if self.cells[y][next] == b'#' {
+                return (x, true);
+            }
+            x = next;
+        }
+    }
+
+    /// Lets water fall from the wet cell at (x, top)
+    fn fall(&mut self, x: usize, top: usize) {
+        let mut y = top;
+        while y + 1 < self.cells.len() && self.cells[y + 1][x] == b'.' {
+            y += 1;
+            self.cells[y][x] = b'|';
+        }
+
+        if y + 1 >= self.cells.len() || !self.supports(x, y + 1) {
+            return;
+        }
+
+        // Fill up the reservoir below us
+        while y > top {
+            let (left, left_wall) = self.spread(x, y, -1);
+            let (right, right_wall) = self.spread(x, y, 1);
+
+            if left_wall && right_wall {
+                self.cells[y][left..=right].fill(b'~');
+                y -= 1;
+                continue;
+            }
+
+            self.cells[y][left..=right].fill(b'|');
+
+            // Overflow the edges, going round again if the water settles
+            let mut resettle = false;
+            for (edge, wall) in [(left, left_wall), (right, right_wall)] {
+                if !wall {
+                    self.fall(edge, y);
+                    resettle |= self.cells[y + 1][edge] == b'~';
+                }
+            }
+
+            if !resettle {
+                break;
+            }
+        }
+    }
+
+    /// Counts cells within the scanned area containing one of the given values
+    fn count(&self, values: &[u8]) -> usize {
+        self.cells[self.min_y..]
+            .iter()
+            .flatten()
+            .filter(|c| values.contains(c))
+            .count()
+    }
+}
+
+/// Parses a range of coordinates
+fn parse_range(s: &str) -> (usize, usize) {
+    match s.split_once("..") {
+        Some((a, b)) => (a.parse().unwrap(), b.parse().unwrap()),
+        None => (s.parse().unwrap(), s.parse().unwrap()),
+    }
+}
+
+/// Parses the ground scan and lets water flow through it
+fn simulate(input: &str) -> Ground {
+    let veins: Vec<((usize, usize), (usize, usize))> = input
+        .lines()
+        .map(|line| {
+            let (a, b) = line.split_once(", ").unwrap();
+            let a_range = parse_range(&a[2..]);
+            let b_range = parse_range(&b[2..]);
+            if a.starts_with('x') {
+                (a_range, b_range)
+            } else {
+                (b_range, a_range)
+            }
+        })
+        .collect();
+
+    let min_x = veins.iter().map(|v| v.0.0).min().unwrap() - 1;
+    let max_x = veins.iter().map(|v| v.0.1).max().unwrap() + 1;
+    let min_y = veins.iter().map(|v| v.1.0).min().unwrap();
+    let max_y = veins.iter().map(|v| v.1.1).max().unwrap();
+
+    let mut cells = vec![vec![b'.'; max_x - min_x + 1]; max_y + 1];
+    for ((x1, x2), (y1, y2)) in veins {
+        for row in &mut cells[y1..=y2] {
+            row[x1 - min_x..=x2 - min_x].fill(b'#');
+        }
+    }
+
+    let mut ground = Ground { cells, min_y };
+    ground.cells[0][500 - min_x] = b'|';
+    ground.fall(500 - min_x, 0);
+    ground
+}
+
+/// Number of tiles reachable by water
+pub fn star1(input: &str) -> String {
+    simulate(input).count(b"|~").to_string()
+}
+
+/// Number of tiles containing water after the spring stops
+pub fn star2(input: &str) -> String {
+    simulate(input).count(b"~").to_string()
 }
@@ -1,7 +1,73 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use std::collections::HashMap;
+
+/// Counts the acres of each type adjacent to (x, y)
+///  Returns (trees, lumberyards)
+fn count_adjacent(area: &[Vec<u8>], x: usize, y: usize) -> (usize, usize) {
+    let neighbours = (y.saturating_sub(1)..=y + 1)
+        .flat_map(|ny| (x.saturating_sub(1)..=x + 1).map(move |nx| (nx, ny)))
+        .filter(|&pos| pos != (x, y));
+
+    let mut counts = (0, 0);
+    for (nx, ny) in neighbours {
+        match area.get(ny).and_then(|row| row.get(nx)) {
+            Some(b'|') => counts.0 += 1,
+            Some(b'#') => counts.1 += 1,
+            _ => (),
+        }
+    }
+    counts
+}
+
+/// Advances the lumber collection area by one minute
+fn step(area: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    (0..area.len())
+        .map(|y| {
+            (0..area[y].len())
+                .map(|x| {
+                    let (trees, lumberyards) = count_adjacent(area, x, y);
+                    match area[y][x] {
+                        b'.' if trees >= 3 => b'|',
+                        b'|' if lumberyards >= 3 => b'#',
+                        b'#' if trees == 0 || lumberyards == 0 => b'.',
+                        acre => acre,
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the resource value of the area after the given number of minutes
+///  Once the area enters a cycle, the remaining minutes are skipped.
+fn resource_value(input: &str, minutes: usize) -> usize {
+    let mut area: Vec<Vec<u8>> = input.lines().map(|l| l.bytes().collect()).collect();
+    let mut seen = HashMap::new();
+
+    let mut minute = 0;
+    while minute < minutes {
+        if let Some(previous) = seen.insert(area.clone(), minute) {
+            let cycle = minute - previous;
+            minute += (minutes - minute) / cycle * cycle;
+            seen.clear();
+            if minute == minutes {
+                break;
+            }
+        }
+
+        area = step(&area);
+        minute += 1;
+    }
+
+    let count = |acre| area.iter().flatten().filter(|&&a| a == acre).count();
+    count(b'|') * count(b'#')
+}
+
+/// Resource value after 10 minutes
+pub fn star1(input: &str) -> String {
+    resource_value(input, 10).to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Resource value after 1 billion minutes
+pub fn star2(input: &str) -> String {
+    resource_value(input, 1_000_000_000).to_string()
 }
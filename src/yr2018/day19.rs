@@ -1,7 +1,26 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use super::device::{ExecutionState, Program};
+
+/// Value of register 0 after the background process halts
+pub fn star1(input: &str) -> String {
+    let program: Program = input.parse().unwrap();
+    let mut state = ExecutionState::new(&program, 0);
+    state.run();
+    state.regs[0].to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Value of register 0 after the background process halts (starting with register 0 = 1)
+///  The program calculates the sum of the divisors of a large number, so
+///  we run the initialization code and then calculate the sum ourselves.
+pub fn star2(input: &str) -> String {
+    let program: Program = input.parse().unwrap();
+    let mut state = ExecutionState::new(&program, 1);
+
+    // Run until the program jumps back to the start of the main loop
+    while state.step() && state.ip != 1 {}
+    let target = *state.regs.iter().max().unwrap();
+
+    (1..=target)
+        .filter(|d| target.is_multiple_of(*d))
+        .sum::<usize>()
+        .to_string()
 }
@@ -1,7 +1,91 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use crate::direction::Direction;
+use nalgebra::Vector2;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// A set of room positions
+type Rooms = HashSet<Vector2<i32>>;
+
+/// Builds the set of doors described by the route regex
+///  Each door is stored as the pair of rooms it connects in both directions.
+fn parse_doors(input: &str) -> HashSet<(Vector2<i32>, Vector2<i32>)> {
+    let mut doors = HashSet::new();
+    let mut current = HashSet::from([Vector2::zeros()]);
+    let mut stack: Vec<(Rooms, Rooms)> = Vec::new();
+
+    for c in input
+        .trim()
+        .trim_start_matches('^')
+        .trim_end_matches('$')
+        .chars()
+    {
+        let dir = match c {
+            'N' => Direction::North,
+            'E' => Direction::East,
+            'S' => Direction::South,
+            'W' => Direction::West,
+            '(' => {
+                stack.push((current.clone(), HashSet::new()));
+                continue;
+            }
+            '|' => {
+                let (starts, ends) = stack.last_mut().unwrap();
+                ends.extend(current);
+                current = starts.clone();
+                continue;
+            }
+            ')' => {
+                let (_, mut ends) = stack.pop().unwrap();
+                ends.extend(current);
+                current = ends;
+                continue;
+            }
+            _ => panic!("invalid character in regex: {c}"),
+        };
+
+        current = current
+            .into_iter()
+            .map(|room| {
+                let next = room + dir.to_vec();
+                doors.insert((room, next));
+                doors.insert((next, room));
+                next
+            })
+            .collect();
+    }
+
+    doors
+}
+
+/// Calculates the number of doors needed to reach every room
+fn room_distances(input: &str) -> HashMap<Vector2<i32>, usize> {
+    let doors = parse_doors(input);
+    let mut distances = HashMap::from([(Vector2::zeros(), 0)]);
+    let mut queue = VecDeque::from([Vector2::zeros()]);
+
+    while let Some(room) = queue.pop_front() {
+        let next_dist = distances[&room] + 1;
+        for dir in Direction::iter() {
+            let next = room + dir.to_vec();
+            if doors.contains(&(room, next)) && !distances.contains_key(&next) {
+                distances.insert(next, next_dist);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Largest number of doors required to reach a room
+pub fn star1(input: &str) -> String {
+    room_distances(input).values().max().unwrap().to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Number of rooms which require passing through at least 1000 doors
+pub fn star2(input: &str) -> String {
+    room_distances(input)
+        .values()
+        .filter(|&&d| d >= 1000)
+        .count()
+        .to_string()
 }
@@ -1,7 +1,58 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use super::device::{Opcode, Program};
+use std::collections::HashSet;
+
+/// Extracts the constants used by the program's hash function
+///  Returns (seed, multiplier)
+fn extract_constants(program: &Program) -> (usize, usize) {
+    let instructions = &program.instructions;
+    let seed_index = instructions
+        .iter()
+        .position(|i| i.opcode == Opcode::Bori && i.b == 0x10000)
+        .expect("could not find hash seed")
+        + 1;
+    let multiplier = instructions
+        .iter()
+        .find(|i| i.opcode == Opcode::Muli)
+        .expect("could not find hash multiplier")
+        .b;
+
+    (instructions[seed_index].a, multiplier)
+}
+
+/// Returns an iterator over the values the program compares with register 0
+///  This is a decompiled version of the input program.
+fn halting_values(input: &str) -> impl Iterator<Item = usize> {
+    let (seed, multiplier) = extract_constants(&input.parse().unwrap());
+    let mut value = 0;
+
+    std::iter::repeat_with(move || {
+        let mut bits = value | 0x10000;
+        value = seed;
+        loop {
+            value = (((value + (bits & 0xFF)) & 0xFFFFFF) * multiplier) & 0xFFFFFF;
+            if bits < 0x100 {
+                break value;
+            }
+            bits >>= 8;
+        }
+    })
+}
+
+/// Lowest value of register 0 which halts after the fewest instructions
+pub fn star1(input: &str) -> String {
+    halting_values(input).next().unwrap().to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Lowest value of register 0 which halts after the most instructions
+pub fn star2(input: &str) -> String {
+    let mut seen = HashSet::new();
+    let mut last = 0;
+    for value in halting_values(input) {
+        if !seen.insert(value) {
+            break;
+        }
+        last = value;
+    }
+
+    last.to_string()
 }
@@ -1,7 +1,108 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Extra space to search beyond the target in the rescue search
+const SEARCH_MARGIN: usize = 100;
+
+/// The cave input parameters
+struct Cave {
+    depth: usize,
+    target: (usize, usize),
+}
+
+impl Cave {
+    /// Parses the cave depth and target
+    fn parse(input: &str) -> Cave {
+        let mut lines = input.lines();
+        let depth = lines.next().unwrap()["depth: ".len()..].parse().unwrap();
+        let (x, y) = lines.next().unwrap()["target: ".len()..]
+            .split_once(',')
+            .unwrap();
+        Cave {
+            depth,
+            target: (x.parse().unwrap(), y.parse().unwrap()),
+        }
+    }
+
+    /// Calculates the region types (0 = rocky, 1 = wet, 2 = narrow) of the given area
+    fn region_types(&self, width: usize, height: usize) -> Vec<Vec<u8>> {
+        let mut erosion = vec![vec![0; width]; height];
+        for y in 0..height {
+            for x in 0..width {
+                let geologic = match (x, y) {
+                    _ if (x, y) == self.target => 0,
+                    (_, 0) => x * 16807,
+                    (0, _) => y * 48271,
+                    _ => erosion[y][x - 1] * erosion[y - 1][x],
+                };
+                erosion[y][x] = (geologic + self.depth) % 20183;
+            }
+        }
+
+        erosion
+            .into_iter()
+            .map(|row| row.into_iter().map(|e| (e % 3) as u8).collect())
+            .collect()
+    }
+}
+
+/// Total risk level of the area between the mouth and the target
+pub fn star1(input: &str) -> String {
+    let cave = Cave::parse(input);
+    cave.region_types(cave.target.0 + 1, cave.target.1 + 1)
+        .iter()
+        .flatten()
+        .map(|&r| usize::from(r))
+        .sum::<usize>()
+        .to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Fewest minutes taken to reach the target
+///  Tools are numbered so that tool N cannot be used in regions of type N
+///  (0 = neither, 1 = torch, 2 = climbing gear).
+pub fn star2(input: &str) -> String {
+    let cave = Cave::parse(input);
+    let width = cave.target.0 + SEARCH_MARGIN;
+    let height = cave.target.1 + SEARCH_MARGIN;
+    let regions = cave.region_types(width, height);
+
+    let mut best = vec![vec![[usize::MAX; 3]; width]; height];
+    let mut queue = BinaryHeap::from([Reverse((0, 0, 0, 1))]);
+    best[0][0][1] = 0;
+
+    while let Some(Reverse((time, x, y, tool))) = queue.pop() {
+        if (x, y, tool) == (cave.target.0, cave.target.1, 1) {
+            return time.to_string();
+        }
+        if time > best[y][x][tool] {
+            continue;
+        }
+
+        let region = usize::from(regions[y][x]);
+        let switch = (0..3).filter(|&t| t != tool && t != region);
+        let moves = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+
+        let next_states = switch.map(|t| (time + 7, x, y, t)).chain(
+            moves
+                .into_iter()
+                .filter(|&(nx, ny)| {
+                    nx < width && ny < height && usize::from(regions[ny][nx]) != tool
+                })
+                .map(|(nx, ny)| (time + 1, nx, ny, tool)),
+        );
+
+        for (next_time, nx, ny, next_tool) in next_states {
+            if next_time < best[ny][nx][next_tool] {
+                best[ny][nx][next_tool] = next_time;
+                queue.push(Reverse((next_time, nx, ny, next_tool)));
+            }
+        }
+    }
+
+    unreachable!()
 }
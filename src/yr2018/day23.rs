@@ -1,7 +1,106 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use crate::vector::VectorExt;
+use nalgebra::Vector3;
+use regex::Regex;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::sync::LazyLock;
+
+/// A nanobot and its signal radius
+struct Nanobot {
+    pos: Vector3<i64>,
+    radius: i64,
+}
+
+impl Nanobot {
+    /// Returns true if this nanobot is in range of any point in the given cube
+    fn in_range_of_cube(&self, corner: Vector3<i64>, size: i64) -> bool {
+        let nearest = self.pos.zip_map(&corner, |p, c| p.clamp(c, c + size - 1));
+        (nearest - self.pos).taxicab_norm() <= self.radius
+    }
+}
+
+/// Parses the input into a list of nanobots
+fn parse_input(input: &str) -> Vec<Nanobot> {
+    static RE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^pos=<([0-9-]+),([0-9-]+),([0-9-]+)>, r=([0-9]+)$").unwrap());
+
+    input
+        .lines()
+        .map(|line| {
+            let parts: Vec<i64> = RE
+                .captures(line)
+                .unwrap()
+                .iter()
+                .skip(1)
+                .map(|m| m.unwrap().as_str().parse().unwrap())
+                .collect();
+
+            Nanobot {
+                pos: Vector3::from_row_slice(&parts[0..3]),
+                radius: parts[3],
+            }
+        })
+        .collect()
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Number of nanobots in range of the strongest nanobot
+pub fn star1(input: &str) -> String {
+    let bots = parse_input(input);
+    let strongest = bots.iter().max_by_key(|b| b.radius).unwrap();
+    bots.iter()
+        .filter(|b| (b.pos - strongest.pos).taxicab_norm() <= strongest.radius)
+        .count()
+        .to_string()
+}
+
+/// Distance to the nearest point in range of the most nanobots
+///  Performs a best-first search, subdividing cubes of space ordered by the
+///  number of nanobots in range of any point within them.
+pub fn star2(input: &str) -> String {
+    let bots = parse_input(input);
+    let count_in_range = |corner: Vector3<i64>, size: i64| {
+        bots.iter()
+            .filter(|b| b.in_range_of_cube(corner, size))
+            .count()
+    };
+
+    // Start with a cube containing all the nanobots
+    let extent = bots
+        .iter()
+        .map(|b| b.pos.abs().max() + b.radius)
+        .max()
+        .unwrap();
+    let mut size = 1;
+    while size < extent * 2 + 1 {
+        size *= 2;
+    }
+
+    let corner = Vector3::repeat(-size / 2);
+    let mut queue = BinaryHeap::from([(
+        count_in_range(corner, size),
+        Reverse(0),
+        Reverse(size),
+        [corner.x, corner.y, corner.z],
+    )]);
+
+    while let Some((_, Reverse(distance), Reverse(size), corner)) = queue.pop() {
+        if size == 1 {
+            return distance.to_string();
+        }
+
+        let half = size / 2;
+        for offset in 0..8 {
+            let sub_corner = Vector3::from(corner)
+                + Vector3::new(offset & 1, (offset >> 1) & 1, offset >> 2) * half;
+            let nearest = sub_corner.map(|c| 0.clamp(c, c + half - 1));
+            queue.push((
+                count_in_range(sub_corner, half),
+                Reverse(nearest.taxicab_norm()),
+                Reverse(half),
+                [sub_corner.x, sub_corner.y, sub_corner.z],
+            ));
+        }
+    }
+
+    unreachable!()
 }
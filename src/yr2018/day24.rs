@@ -1,7 +1,161 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use regex::Regex;
+use std::cmp::Reverse;
+use std::sync::LazyLock;
+
+/// A group of units in one of the armies
+#[derive(Clone, Debug)]
+struct Group {
+    infection: bool,
+    units: u32,
+    hp: u32,
+    weaknesses: Vec<String>,
+    immunities: Vec<String>,
+    damage: u32,
+    attack_type: String,
+    initiative: u32,
+}
+
+impl Group {
+    /// Returns the effective power of the group
+    fn power(&self) -> u32 {
+        self.units * self.damage
+    }
+
+    /// Returns the damage this group would deal to the target group
+    fn damage_to(&self, target: &Group) -> u32 {
+        if target.immunities.contains(&self.attack_type) {
+            0
+        } else if target.weaknesses.contains(&self.attack_type) {
+            self.power() * 2
+        } else {
+            self.power()
+        }
+    }
+}
+
+/// Parses the input into a list of groups
+fn parse_input(input: &str) -> Vec<Group> {
+    static RE: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"^(\d+) units each with (\d+) hit points (?:\(([^)]*)\) )?with an attack that does (\d+) (\w+) damage at initiative (\d+)$").unwrap()
+    });
+
+    let mut infection = false;
+    let mut groups = Vec::new();
+    for line in input.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        match line {
+            "Immune System:" => infection = false,
+            "Infection:" => infection = true,
+            _ => {
+                let caps = RE.captures(line).unwrap();
+                let mut group = Group {
+                    infection,
+                    units: caps[1].parse().unwrap(),
+                    hp: caps[2].parse().unwrap(),
+                    weaknesses: Vec::new(),
+                    immunities: Vec::new(),
+                    damage: caps[4].parse().unwrap(),
+                    attack_type: caps[5].to_string(),
+                    initiative: caps[6].parse().unwrap(),
+                };
+
+                for modifier in caps.get(3).iter().flat_map(|m| m.as_str().split("; ")) {
+                    let (list, types) = if let Some(types) = modifier.strip_prefix("weak to ") {
+                        (&mut group.weaknesses, types)
+                    } else {
+                        (
+                            &mut group.immunities,
+                            modifier.trim_start_matches("immune to "),
+                        )
+                    };
+                    list.extend(types.split(", ").map(str::to_string));
+                }
+
+                groups.push(group);
+            }
+        }
+    }
+
+    groups
 }
 
-pub fn star2(_input: &str) -> String {
-    unimplemented!()
+/// Runs a single round of the fight
+///  Returns false if the fight is in a stalemate (no units were killed).
+fn fight_round(groups: &mut [Group]) -> bool {
+    // Target selection
+    let mut order: Vec<usize> = (0..groups.len()).collect();
+    order.sort_by_key(|&i| Reverse((groups[i].power(), groups[i].initiative)));
+
+    let mut targets: Vec<Option<usize>> = vec![None; groups.len()];
+    for &attacker in &order {
+        let group = &groups[attacker];
+        targets[attacker] = (0..groups.len())
+            .filter(|&t| groups[t].infection != group.infection && !targets.contains(&Some(t)))
+            .filter(|&t| group.damage_to(&groups[t]) > 0)
+            .max_by_key(|&t| {
+                (
+                    group.damage_to(&groups[t]),
+                    groups[t].power(),
+                    groups[t].initiative,
+                )
+            });
+    }
+
+    // Attacking
+    order.sort_by_key(|&i| Reverse(groups[i].initiative));
+
+    let mut any_killed = false;
+    for attacker in order {
+        if groups[attacker].units == 0 {
+            continue;
+        }
+
+        if let Some(target) = targets[attacker] {
+            let killed = (groups[attacker].damage_to(&groups[target]) / groups[target].hp)
+                .min(groups[target].units);
+            groups[target].units -= killed;
+            any_killed |= killed > 0;
+        }
+    }
+
+    any_killed
+}
+
+/// Fights the battle until one army wins
+///  Returns the winning army (true for infection) and the number of units it
+///  has left, or None if there is a stalemate.
+fn fight(mut groups: Vec<Group>) -> Option<(bool, u32)> {
+    loop {
+        groups.retain(|g| g.units > 0);
+        if groups.iter().all(|g| g.infection == groups[0].infection) {
+            return Some((groups[0].infection, groups.iter().map(|g| g.units).sum()));
+        }
+
+        if !fight_round(&mut groups) {
+            return None;
+        }
+    }
+}
+
+/// Number of units the winning army has left
+pub fn star1(input: &str) -> String {
+    fight(parse_input(input)).unwrap().1.to_string()
+}
+
+/// Number of units the immune system has left with the smallest boost which wins
+pub fn star2(input: &str) -> String {
+    let groups = parse_input(input);
+    (1..)
+        .find_map(|boost| {
+            let mut boosted = groups.clone();
+            for group in boosted.iter_mut().filter(|g| !g.infection) {
+                group.damage += boost;
+            }
+
+            match fight(boosted) {
+                Some((false, units)) => Some(units),
+                _ => None,
+            }
+        })
+        .unwrap()
+        .to_string()
 }
@@ -1,3 +1,39 @@
-pub fn star1(_input: &str) -> String {
-    unimplemented!()
+use crate::vector::VectorExt;
+use nalgebra::Vector4;
+
+/// Finds the root of a point in the disjoint set forest
+fn find_root(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
+
+/// Number of constellations formed by the points
+pub fn star1(input: &str) -> String {
+    let points: Vec<Vector4<i32>> = input
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|line| {
+            let coords: Vec<i32> = line.split(',').map(|c| c.trim().parse().unwrap()).collect();
+            Vector4::from_row_slice(&coords)
+        })
+        .collect();
+
+    let mut parents: Vec<usize> = (0..points.len()).collect();
+    let mut constellations = points.len();
+    for i in 0..points.len() {
+        for j in i + 1..points.len() {
+            if (points[i] - points[j]).taxicab_norm() <= 3 {
+                let (a, b) = (find_root(&mut parents, i), find_root(&mut parents, j));
+                if a != b {
+                    parents[a] = b;
+                    constellations -= 1;
+                }
+            }
+        }
+    }
+
+    constellations.to_string()
 }
@@ -0,0 +1,153 @@
+//! The wrist device processor used in days 16, 19 and 21
+use std::str::FromStr;
+use strum::{EnumIter, EnumString};
+
+/// The register file of the device
+pub type Registers = [usize; 6];
+
+/// All the possible opcodes
+#[derive(Clone, Copy, Debug, EnumIter, EnumString, Eq, Hash, PartialEq)]
+#[strum(serialize_all = "lowercase")]
+pub enum Opcode {
+    Addr,
+    Addi,
+    Mulr,
+    Muli,
+    Banr,
+    Bani,
+    Borr,
+    Bori,
+    Setr,
+    Seti,
+    Gtir,
+    Gtri,
+    Gtrr,
+    Eqir,
+    Eqri,
+    Eqrr,
+}
+
+impl Opcode {
+    /// Executes this opcode with the given operands
+    ///  Returns None if a register operand is out of range.
+    pub fn execute(self, regs: &mut [usize], a: usize, b: usize, c: usize) -> Option<()> {
+        let reg = |r: usize| regs.get(r).copied();
+        let value = match self {
+            Opcode::Addr => reg(a)? + reg(b)?,
+            Opcode::Addi => reg(a)? + b,
+            Opcode::Mulr => reg(a)? * reg(b)?,
+            Opcode::Muli => reg(a)? * b,
+            Opcode::Banr => reg(a)? & reg(b)?,
+            Opcode::Bani => reg(a)? & b,
+            Opcode::Borr => reg(a)? | reg(b)?,
+            Opcode::Bori => reg(a)? | b,
+            Opcode::Setr => reg(a)?,
+            Opcode::Seti => a,
+            Opcode::Gtir => usize::from(a > reg(b)?),
+            Opcode::Gtri => usize::from(reg(a)? > b),
+            Opcode::Gtrr => usize::from(reg(a)? > reg(b)?),
+            Opcode::Eqir => usize::from(a == reg(b)?),
+            Opcode::Eqri => usize::from(reg(a)? == b),
+            Opcode::Eqrr => usize::from(reg(a)? == reg(b)?),
+        };
+
+        *regs.get_mut(c)? = value;
+        Some(())
+    }
+}
+
+/// A single instruction
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub a: usize,
+    pub b: usize,
+    pub c: usize,
+}
+
+impl FromStr for Instruction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Instruction, ()> {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        if parts.len() != 4 {
+            return Err(());
+        }
+
+        let operand = |i: usize| parts[i].parse().map_err(|_| ());
+        Ok(Instruction {
+            opcode: parts[0].parse().map_err(|_| ())?,
+            a: operand(1)?,
+            b: operand(2)?,
+            c: operand(3)?,
+        })
+    }
+}
+
+/// A program with an instruction pointer bound to a register
+#[derive(Clone, Debug)]
+pub struct Program {
+    pub ip_reg: usize,
+    pub instructions: Vec<Instruction>,
+}
+
+impl FromStr for Program {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Program, ()> {
+        let mut lines = s.lines();
+        let ip_reg = lines
+            .next()
+            .and_then(|l| l.strip_prefix("#ip "))
+            .ok_or(())?
+            .trim()
+            .parse()
+            .map_err(|_| ())?;
+
+        Ok(Program {
+            ip_reg,
+            instructions: lines.map(str::parse).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// Execution state of a program
+#[derive(Clone, Debug)]
+pub struct ExecutionState<'a> {
+    pub program: &'a Program,
+    pub ip: usize,
+    pub regs: Registers,
+}
+
+impl ExecutionState<'_> {
+    /// Creates a new execution state with the given initial register 0
+    pub fn new(program: &Program, reg0: usize) -> ExecutionState<'_> {
+        let mut regs = [0; 6];
+        regs[0] = reg0;
+        ExecutionState {
+            program,
+            ip: 0,
+            regs,
+        }
+    }
+
+    /// Executes one instruction
+    ///  Returns false if the program has halted.
+    pub fn step(&mut self) -> bool {
+        let Some(inst) = self.program.instructions.get(self.ip) else {
+            return false;
+        };
+
+        self.regs[self.program.ip_reg] = self.ip;
+        inst.opcode
+            .execute(&mut self.regs, inst.a, inst.b, inst.c)
+            .expect("invalid register");
+        self.ip = self.regs[self.program.ip_reg] + 1;
+        true
+    }
+
+    /// Runs the program until it halts
+    pub fn run(&mut self) {
+        while self.step() {}
+    }
+}
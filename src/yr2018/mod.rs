@@ -25,6 +25,7 @@ mod day6;
 mod day7;
 mod day8;
 mod day9;
+mod device;
 
 use crate::StarFunction;
 use crate::StarId;
@@ -1,81 +1,335 @@
+use std::fmt;
+
+/// A binary operator
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-enum Token {
-    Num(u8),
+enum Operator {
     Add,
+    Sub,
     Mul,
+    Div,
+}
+
+/// Which way operators with the same precedence group
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Associativity {
+    Left,
+    /// Not needed by either puzzle table, but available for other variants
+    #[allow(dead_code)]
+    Right,
+}
+
+/// Precedence and associativity of each supported operator
+///  Higher precedence operators bind more tightly. Operators which are not
+///  in the table are rejected by the parser.
+type PrecedenceTable = [(Operator, u8, Associativity)];
+
+/// All operators evaluated left to right
+const LEFT_TO_RIGHT: [(Operator, u8, Associativity); 4] = [
+    (Operator::Add, 1, Associativity::Left),
+    (Operator::Sub, 1, Associativity::Left),
+    (Operator::Mul, 1, Associativity::Left),
+    (Operator::Div, 1, Associativity::Left),
+];
+
+/// Addition and subtraction evaluated before multiplication and division
+const ADDITION_FIRST: [(Operator, u8, Associativity); 4] = [
+    (Operator::Add, 2, Associativity::Left),
+    (Operator::Sub, 2, Associativity::Left),
+    (Operator::Mul, 1, Associativity::Left),
+    (Operator::Div, 1, Associativity::Left),
+];
+
+impl Operator {
+    fn symbol(self) -> char {
+        match self {
+            Operator::Add => '+',
+            Operator::Sub => '-',
+            Operator::Mul => '*',
+            Operator::Div => '/',
+        }
+    }
+
+    /// Applies the operator (division truncates towards zero)
+    ///  Returns None on overflow or division by zero.
+    fn apply(self, a: i64, b: i64) -> Option<i64> {
+        match self {
+            Operator::Add => a.checked_add(b),
+            Operator::Sub => a.checked_sub(b),
+            Operator::Mul => a.checked_mul(b),
+            Operator::Div => a.checked_div(b),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Token {
+    Num(i64),
+    Op(Operator),
     Open,
     Close,
 }
 
-fn parse(line: &str) -> impl Iterator<Item = Token> + '_ {
-    line.chars()
-        .filter(|&c| !c.is_ascii_whitespace())
-        .map(|c| match c {
-            '0'..='9' => Token::Num(c as u8 - b'0'),
-            '+' => Token::Add,
-            '*' => Token::Mul,
-            '(' => Token::Open,
-            ')' => Token::Close,
-            _ => panic!("invalid character {c}"),
-        })
+/// An expression syntax tree
+///  Each operation stores the position of its operator in the input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Expr {
+    Num(i64),
+    BinOp(Operator, usize, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn evaluate(&self) -> Result<i64, ExprError> {
+        match self {
+            Expr::Num(n) => Ok(*n),
+            Expr::BinOp(op, position, a, b) => {
+                let (a, b) = (a.evaluate()?, b.evaluate()?);
+                op.apply(a, b).ok_or_else(|| ExprError {
+                    position: *position,
+                    message: if *op == Operator::Div && b == 0 {
+                        "division by zero".to_string()
+                    } else {
+                        "arithmetic overflow".to_string()
+                    },
+                })
+            }
+        }
+    }
+}
+
+/// Displays the expression with every operation in parentheses
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Num(n) => write!(f, "{n}"),
+            Expr::BinOp(op, _, a, b) => write!(f, "({a} {} {b})", op.symbol()),
+        }
+    }
+}
+
+/// An error encountered while parsing or evaluating an expression
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct ExprError {
+    /// Byte offset of the error in the input
+    position: usize,
+    message: String,
 }
 
-fn pop_ops(values: &mut Vec<u64>, ops: &mut Vec<Token>, pop_mul: bool) {
-    loop {
-        let f = match ops.last() {
-            Some(Token::Add) => |a, b| a + b,
-            Some(Token::Mul) if pop_mul => |a, b| a * b,
-            _ => return,
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+/// Splits a line into tokens along with their positions
+fn tokenize(line: &str) -> Result<Vec<(usize, Token)>, ExprError> {
+    let mut tokens = Vec::new();
+    let mut chars = line.char_indices().peekable();
+
+    while let Some((pos, c)) = chars.next() {
+        let token = match c {
+            _ if c.is_ascii_whitespace() => continue,
+            '0'..='9' => {
+                let mut end = pos + 1;
+                while let Some((i, _)) = chars.next_if(|(_, d)| d.is_ascii_digit()) {
+                    end = i + 1;
+                }
+                Token::Num(line[pos..end].parse().map_err(|_| ExprError {
+                    position: pos,
+                    message: "number too large".to_string(),
+                })?)
+            }
+            '+' => Token::Op(Operator::Add),
+            '-' => Token::Op(Operator::Sub),
+            '*' => Token::Op(Operator::Mul),
+            '/' => Token::Op(Operator::Div),
+            '(' => Token::Open,
+            ')' => Token::Close,
+            _ => {
+                return Err(ExprError {
+                    position: pos,
+                    message: format!("invalid character {c}"),
+                });
+            }
         };
 
-        ops.pop();
-        let v2 = values.pop().unwrap();
-        let v1 = values.last_mut().unwrap();
-        *v1 = f(*v1, v2);
+        tokens.push((pos, token));
     }
+
+    Ok(tokens)
 }
 
-fn evaluate(line: &str, add_precedence: bool) -> u64 {
-    let mut values = Vec::new();
-    let mut ops = Vec::new();
+/// Precedence climbing parser
+struct Parser<'a> {
+    table: &'a PrecedenceTable,
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+    /// Position of the end of the input
+    end: usize,
+}
 
-    for t in parse(line) {
-        match t {
-            Token::Num(n) => values.push(n.into()),
-            Token::Open => ops.push(t),
-            Token::Add => {
-                pop_ops(&mut values, &mut ops, !add_precedence);
-                ops.push(t);
+impl Parser<'_> {
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, ExprError> {
+        Err(ExprError {
+            position: self.tokens.get(self.next).map_or(self.end, |t| t.0),
+            message: message.into(),
+        })
+    }
+
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.next).map(|t| t.1)
+    }
+
+    /// Parses a number or bracketed expression
+    fn primary(&mut self) -> Result<Expr, ExprError> {
+        match self.peek() {
+            Some(Token::Num(n)) => {
+                self.next += 1;
+                Ok(Expr::Num(n))
             }
-            Token::Mul => {
-                pop_ops(&mut values, &mut ops, true);
-                ops.push(t);
+            Some(Token::Open) => {
+                self.next += 1;
+                let expr = self.expression(0)?;
+                if self.peek() != Some(Token::Close) {
+                    return self.error("expected ')'");
+                }
+                self.next += 1;
+                Ok(expr)
             }
-            Token::Close => {
-                pop_ops(&mut values, &mut ops, true);
-                assert_eq!(ops.pop(), Some(Token::Open));
+            Some(_) => self.error("expected number or '('"),
+            None => self.error("unexpected end of expression"),
+        }
+    }
+
+    /// Parses an expression containing operators with at least the given precedence
+    fn expression(&mut self, min_precedence: u8) -> Result<Expr, ExprError> {
+        let mut lhs = self.primary()?;
+
+        while let Some(Token::Op(op)) = self.peek() {
+            let Some(&(_, precedence, associativity)) =
+                self.table.iter().find(|&&(o, _, _)| o == op)
+            else {
+                return self.error(format!("unsupported operator {}", op.symbol()));
+            };
+
+            if precedence < min_precedence {
+                break;
             }
+
+            let position = self.tokens[self.next].0;
+            self.next += 1;
+            let rhs_precedence = match associativity {
+                Associativity::Left => precedence + 1,
+                Associativity::Right => precedence,
+            };
+            let rhs = self.expression(rhs_precedence)?;
+            lhs = Expr::BinOp(op, position, Box::new(lhs), Box::new(rhs));
         }
+
+        Ok(lhs)
     }
+}
 
-    pop_ops(&mut values, &mut ops, true);
-    assert!(ops.is_empty());
-    assert_eq!(values.len(), 1);
-    values[0]
+/// Parses a line into an expression using the given operator precedences
+fn parse(line: &str, table: &PrecedenceTable) -> Result<Expr, ExprError> {
+    let mut parser = Parser {
+        table,
+        tokens: tokenize(line)?,
+        next: 0,
+        end: line.len(),
+    };
+
+    let expr = parser.expression(0)?;
+    if parser.peek().is_some() {
+        return parser.error("unexpected token");
+    }
+    Ok(expr)
 }
 
-fn run(input: &str, add_precedence: bool) -> String {
+fn run(input: &str, table: &PrecedenceTable) -> String {
     input
         .lines()
-        .map(|l| evaluate(l, add_precedence))
-        .sum::<u64>()
+        .map(|l| {
+            parse(l, table)
+                .and_then(|expr| expr.evaluate())
+                .unwrap_or_else(|e| panic!("invalid expression {l}: {e}"))
+        })
+        .try_fold(0, i64::checked_add)
+        .expect("sum of results is too large")
         .to_string()
 }
 
 pub fn star1(input: &str) -> String {
-    run(input, false)
+    run(input, &LEFT_TO_RIGHT)
 }
 
 pub fn star2(input: &str) -> String {
-    run(input, true)
+    run(input, &ADDITION_FIRST)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn show(line: &str, table: &PrecedenceTable) -> String {
+        parse(line, table).unwrap().to_string()
+    }
+
+    #[test]
+    fn precedence_tables() {
+        let line = "1 + 2 * 3 + 4 * 5 + 6";
+        assert_eq!(
+            show(line, &LEFT_TO_RIGHT),
+            "(((((1 + 2) * 3) + 4) * 5) + 6)"
+        );
+        assert_eq!(
+            show(line, &ADDITION_FIRST),
+            "(((1 + 2) * (3 + 4)) * (5 + 6))"
+        );
+        assert_eq!(parse(line, &LEFT_TO_RIGHT).unwrap().evaluate(), Ok(71));
+        assert_eq!(parse(line, &ADDITION_FIRST).unwrap().evaluate(), Ok(231));
+    }
+
+    #[test]
+    fn custom_table() {
+        // Conventional precedence with right associative subtraction
+        let table = [
+            (Operator::Add, 1, Associativity::Left),
+            (Operator::Sub, 1, Associativity::Right),
+            (Operator::Mul, 2, Associativity::Left),
+            (Operator::Div, 2, Associativity::Left),
+        ];
+
+        let expr = parse("100 - 20 - 3 * (14 / 7)", &table).unwrap();
+        assert_eq!(expr.to_string(), "(100 - (20 - (3 * (14 / 7))))");
+        assert_eq!(expr.evaluate(), Ok(86));
+    }
+
+    #[test]
+    fn errors() {
+        let position = |s: &str| parse(s, &LEFT_TO_RIGHT).unwrap_err().position;
+        assert_eq!(position("1 + (2 * 3"), 10);
+        assert_eq!(position("1 + * 2"), 4);
+        assert_eq!(position("1 2"), 2);
+        assert_eq!(position("1 % 2"), 2);
+
+        let only_add = [(Operator::Add, 1, Associativity::Left)];
+        assert_eq!(parse("1 + 2 - 3", &only_add).unwrap_err().position, 6);
+    }
+
+    #[test]
+    fn evaluation_errors() {
+        let error = |s: &str| parse(s, &LEFT_TO_RIGHT).unwrap().evaluate().unwrap_err();
+        assert_eq!(
+            error("1 + (2 / (3 - 3))"),
+            ExprError {
+                position: 7,
+                message: "division by zero".to_string()
+            }
+        );
+        assert_eq!(error("9223372036854775807 + 1").position, 20);
+        assert_eq!(
+            error("(0 - 9223372036854775807 - 1) / (0 - 1)").position,
+            30
+        );
+    }
 }
@@ -1,3 +1,13 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::options;
+
+/// Number of rule ids which can be used in the input
+///  Rules created while parsing alternatives and sequences are given ids
+///  after these.
+const NAMED_RULES: usize = 150;
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum Rule {
     Terminal(u8),
@@ -31,7 +41,7 @@ fn parse_rule(rules: &mut Vec<Rule>, s: &str) -> Rule {
 }
 
 fn parse_rules(rules_str: &str) -> Vec<Rule> {
-    let mut rules = vec![Rule::Alias(0); 150];
+    let mut rules = vec![Rule::Alias(0); NAMED_RULES];
     for rule in rules_str.lines() {
         let (num_str, rest) = rule.split_once(':').unwrap();
         let id: usize = num_str.parse().unwrap();
@@ -40,42 +50,251 @@ fn parse_rules(rules_str: &str) -> Vec<Rule> {
     rules
 }
 
-fn append_dedup<T: Ord>(v: &mut Vec<T>, iter: impl IntoIterator<Item = T>) {
-    for value in iter {
-        if !v.contains(&value) {
-            v.push(value);
+/// A symbol on the right hand side of a production
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Symbol {
+    Terminal(u8),
+    Rule(usize),
+}
+
+/// The rules converted into a list of productions for the Earley parser
+struct Grammar {
+    /// (rule, symbols) for each production
+    productions: Vec<(usize, Vec<Symbol>)>,
+    /// Indices of the productions for each rule
+    by_rule: Vec<Vec<usize>>,
+}
+
+/// An Earley item: a production, how much of it has been matched, and where
+/// the match started
+type Item = (usize, usize, usize);
+
+impl Grammar {
+    fn new(rules: &[Rule]) -> Grammar {
+        let mut productions = Vec::new();
+        let mut by_rule = vec![Vec::new(); rules.len()];
+        for (id, rule) in rules.iter().enumerate() {
+            let alternatives = match *rule {
+                Rule::Terminal(c) => vec![vec![Symbol::Terminal(c)]],
+                Rule::Alias(a) => vec![vec![Symbol::Rule(a)]],
+                Rule::Concat(a, b) => vec![vec![Symbol::Rule(a), Symbol::Rule(b)]],
+                Rule::Choice(a, b) => vec![vec![Symbol::Rule(a)], vec![Symbol::Rule(b)]],
+            };
+
+            for symbols in alternatives {
+                by_rule[id].push(productions.len());
+                productions.push((id, symbols));
+            }
+        }
+
+        Grammar {
+            productions,
+            by_rule,
+        }
+    }
+
+    /// Builds the Earley chart for a message starting from rule 0
+    ///  chart[i] contains the items which have matched message[..i]. No rule
+    ///  can match the empty string, so completion never needs to look at
+    ///  items created later in the same chart entry.
+    fn chart(&self, message: &[u8]) -> Vec<HashSet<Item>> {
+        let mut chart = vec![HashSet::new(); message.len() + 1];
+        chart[0].extend(self.by_rule[0].iter().map(|&p| (p, 0, 0)));
+
+        for i in 0..=message.len() {
+            let mut queue: Vec<Item> = chart[i].iter().copied().collect();
+            while let Some(item) = queue.pop() {
+                let (production, dot, origin) = item;
+                let (rule, symbols) = &self.productions[production];
+                match symbols.get(dot) {
+                    // Predict
+                    Some(&Symbol::Rule(next)) => {
+                        for &p in &self.by_rule[next] {
+                            if chart[i].insert((p, 0, i)) {
+                                queue.push((p, 0, i));
+                            }
+                        }
+                    }
+                    // Scan
+                    Some(&Symbol::Terminal(c)) => {
+                        if message.get(i) == Some(&c) {
+                            chart[i + 1].insert((production, dot + 1, origin));
+                        }
+                    }
+                    // Complete
+                    None => {
+                        let waiting: Vec<Item> = chart[origin]
+                            .iter()
+                            .filter(|&&(p, d, _)| {
+                                self.productions[p].1.get(d) == Some(&Symbol::Rule(*rule))
+                            })
+                            .map(|&(p, d, o)| (p, d + 1, o))
+                            .collect();
+
+                        for next in waiting {
+                            if chart[i].insert(next) {
+                                queue.push(next);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        chart
+    }
+}
+
+/// A parse tree for a message
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum ParseTree {
+    Terminal(u8),
+    /// A named rule and the trees it was made from
+    ///  Rules created internally while parsing the rules are flattened into
+    ///  their parents.
+    Rule(usize, Vec<ParseTree>),
+}
+
+impl ParseTree {
+    /// Writes this tree with one node per line, indented by depth
+    fn write_indented(&self, f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+        let indent = depth * 2;
+        match self {
+            ParseTree::Terminal(c) => writeln!(f, "{:indent$}\"{}\"", "", char::from(*c)),
+            ParseTree::Rule(id, children) => {
+                writeln!(f, "{:indent$}{id}", "")?;
+                for child in children {
+                    child.write_indented(f, depth + 1)?;
+                }
+                Ok(())
+            }
         }
     }
 }
 
-fn matches(rules: &[Rule], id: usize, s: &[u8]) -> Vec<usize> {
-    match rules[id] {
-        Rule::Terminal(c) if s.first() == Some(&c) => vec![1],
-        Rule::Terminal(_) => Vec::new(),
-        Rule::Alias(a) => matches(rules, a, s),
-        Rule::Concat(a, b) => {
-            let mut offs = Vec::new();
-            for off_a in matches(rules, a, s) {
-                append_dedup(
-                    &mut offs,
-                    matches(rules, b, &s[off_a..])
-                        .into_iter()
-                        .map(move |o| o + off_a),
-                );
+impl fmt::Display for ParseTree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_indented(f, 0)
+    }
+}
+
+/// Builds parse trees from a completed Earley chart
+struct TreeBuilder<'a> {
+    grammar: &'a Grammar,
+    chart: &'a [HashSet<Item>],
+    message: &'a [u8],
+    /// Spans being built further up the tree, used to avoid cycles of unit rules
+    in_progress: HashSet<(usize, usize, usize)>,
+}
+
+impl TreeBuilder<'_> {
+    /// Builds the trees for a rule matching message[start..end]
+    ///  Returns the rule's node, or its children for internal rules.
+    fn rule(&mut self, rule: usize, start: usize, end: usize) -> Option<Vec<ParseTree>> {
+        if !self.in_progress.insert((rule, start, end)) {
+            return None;
+        }
+
+        let grammar = self.grammar;
+        let result = grammar.by_rule[rule].iter().find_map(|&production| {
+            let item = (production, grammar.productions[production].1.len(), start);
+            if self.chart[end].contains(&item) {
+                self.production(production, start, end)
+            } else {
+                None
             }
-            offs
+        });
+
+        self.in_progress.remove(&(rule, start, end));
+        let children = result?;
+        if rule < NAMED_RULES {
+            Some(vec![ParseTree::Rule(rule, children)])
+        } else {
+            Some(children)
         }
-        Rule::Choice(a, b) => {
-            let mut offs = matches(rules, a, s);
-            append_dedup(&mut offs, matches(rules, b, s));
-            offs
+    }
+
+    /// Builds the trees for the symbols of a complete production
+    ///  This works backwards from the end, using the chart to find where
+    ///  each symbol can start.
+    fn production(
+        &mut self,
+        production: usize,
+        start: usize,
+        end: usize,
+    ) -> Option<Vec<ParseTree>> {
+        let symbols = &self.grammar.productions[production].1;
+        let mut parts = Vec::new();
+        let mut pos = end;
+
+        for dot in (0..symbols.len()).rev() {
+            match symbols[dot] {
+                Symbol::Terminal(c) => {
+                    pos -= 1;
+                    if self.message[pos] != c {
+                        return None;
+                    }
+                    parts.push(vec![ParseTree::Terminal(c)]);
+                }
+                Symbol::Rule(rule) => {
+                    let (split, trees) = (start..pos).rev().find_map(|split| {
+                        let prefix_matches = if dot == 0 {
+                            split == start
+                        } else {
+                            self.chart[split].contains(&(production, dot, start))
+                        };
+                        if prefix_matches {
+                            Some((split, self.rule(rule, split, pos)?))
+                        } else {
+                            None
+                        }
+                    })?;
+                    pos = split;
+                    parts.push(trees);
+                }
+            }
         }
+
+        parts.reverse();
+        Some(parts.into_iter().flatten().collect())
     }
 }
 
+/// Returns true if rule 0 matches the whole message
+fn is_match(grammar: &Grammar, message: &[u8]) -> bool {
+    let chart = grammar.chart(message);
+    grammar.by_rule[0].iter().any(|&p| {
+        let len = grammar.productions[p].1.len();
+        chart[message.len()].contains(&(p, len, 0))
+    })
+}
+
+/// Returns a parse tree for the message if rule 0 matches it
+fn parse_tree(grammar: &Grammar, message: &[u8]) -> Option<ParseTree> {
+    let chart = grammar.chart(message);
+    let mut builder = TreeBuilder {
+        grammar,
+        chart: &chart,
+        message,
+        in_progress: HashSet::new(),
+    };
+
+    builder.rule(0, 0, message.len())?.pop()
+}
+
+/// Counts the messages which match rule 0
+///  In verbose mode, the parse tree of each matching message is printed.
 fn count_matches(rules: &[Rule], msgs: &str) -> String {
+    let grammar = Grammar::new(rules);
     msgs.lines()
-        .filter(|&line| matches(rules, 0, line.as_bytes()).contains(&line.len()))
+        .filter(|&line| {
+            if options::verbose()
+                && let Some(tree) = parse_tree(&grammar, line.as_bytes())
+            {
+                eprintln!("{line}\n{tree}");
+            }
+            is_match(&grammar, line.as_bytes())
+        })
         .count()
         .to_string()
 }
@@ -88,13 +307,53 @@ pub fn star1(input: &str) -> String {
 pub fn star2(input: &str) -> String {
     let (rules_str, msgs) = input.split_once("\n\n").unwrap();
     let mut rules = parse_rules(rules_str);
+    rules[8] = parse_rule(&mut rules, "42 | 42 8");
+    rules[11] = parse_rule(&mut rules, "42 31 | 42 11 31");
+    count_matches(&rules, msgs)
+}
 
-    rules.push(Rule::Concat(42, 8));
-    rules[8] = Rule::Choice(42, rules.len() - 1);
-    rules.push(Rule::Concat(11, 31));
-    rules.push(Rule::Concat(42, rules.len() - 1));
-    rules.push(Rule::Concat(42, 31));
-    rules[11] = Rule::Choice(rules.len() - 1, rules.len() - 2);
+#[cfg(test)]
+mod test {
+    use super::*;
 
-    count_matches(&rules, msgs)
+    fn grammar(rules: &str) -> Grammar {
+        Grammar::new(&parse_rules(rules))
+    }
+
+    #[test]
+    fn left_recursion() {
+        // Rule 0 is one or more "a"s followed by a "b", built left recursively
+        let g = grammar("0: 1 3\n1: 1 2 | 2\n2: \"a\"\n3: \"b\"");
+        assert!(is_match(&g, b"ab"));
+        assert!(is_match(&g, b"aaaab"));
+        assert!(!is_match(&g, b"b"));
+        assert!(!is_match(&g, b"aaba"));
+    }
+
+    #[test]
+    fn unit_cycles() {
+        let g = grammar("0: 1 | 2\n1: 0 | 2\n2: \"a\"");
+        assert!(is_match(&g, b"a"));
+        assert!(!is_match(&g, b"aa"));
+        assert_eq!(
+            parse_tree(&g, b"a"),
+            Some(ParseTree::Rule(
+                0,
+                vec![ParseTree::Rule(
+                    1,
+                    vec![ParseTree::Rule(2, vec![ParseTree::Terminal(b'a')])]
+                )]
+            ))
+        );
+    }
+
+    #[test]
+    fn tree() {
+        let g = grammar("0: 1 1 | 0 1 1\n1: 2 | 3\n2: \"a\"\n3: \"b\"");
+        assert_eq!(parse_tree(&g, b"aab"), None);
+        assert_eq!(
+            parse_tree(&g, b"abba").unwrap().to_string(),
+            "0\n  0\n    1\n      2\n        \"a\"\n    1\n      3\n        \"b\"\n  1\n    3\n      \"b\"\n  1\n    2\n      \"a\"\n"
+        );
+    }
 }
@@ -1,7 +1,163 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use std::collections::HashMap;
+
+use nalgebra::DMatrix;
+
+use crate::dihedral::Dihedral;
+
+type Image = DMatrix<bool>;
+
+const MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+fn parse_tiles(input: &str) -> Vec<(u64, Image)> {
+    input
+        .split("\n\n")
+        .map(|block| {
+            let (header, body) = block.split_once('\n').unwrap();
+            let id = header
+                .trim_start_matches("Tile ")
+                .trim_end_matches(':')
+                .parse()
+                .unwrap();
+            let size = body.lines().count();
+            let tile = DMatrix::from_row_iterator(
+                size,
+                size,
+                body.bytes().filter(|&b| b != b'\n').map(|b| b == b'#'),
+            );
+            (id, tile)
+        })
+        .collect()
+}
+
+/// Returns all 8 dihedral transforms (rotations and reflections) of a matrix
+fn transforms(m: &Image) -> Vec<Image> {
+    Dihedral::all().map(|d| d.transform_matrix(m)).collect()
+}
+
+/// Converts an edge to a number which is the same whichever way round it is
+fn edge_key<'a>(edge: impl DoubleEndedIterator<Item = &'a bool> + Clone) -> u16 {
+    let fold = |acc, &b| (acc << 1) | u16::from(b);
+    edge.clone().fold(0, fold).min(edge.rev().fold(0, fold))
+}
+
+fn edge_keys(tile: &Image) -> [u16; 4] {
+    [
+        edge_key(tile.row(0).iter()),
+        edge_key(tile.row(tile.nrows() - 1).iter()),
+        edge_key(tile.column(0).iter()),
+        edge_key(tile.column(tile.ncols() - 1).iter()),
+    ]
+}
+
+fn count_edges(tiles: &[(u64, Image)]) -> HashMap<u16, usize> {
+    let mut counts = HashMap::new();
+    for (_, tile) in tiles {
+        for key in edge_keys(tile) {
+            *counts.entry(key).or_default() += 1;
+        }
+    }
+    counts
+}
+
+/// Returns the number of edges of the tile which do not match any other tile
+fn unmatched_edges(counts: &HashMap<u16, usize>, tile: &Image) -> usize {
+    edge_keys(tile).iter().filter(|k| counts[k] == 1).count()
+}
+
+pub fn star1(input: &str) -> String {
+    let tiles = parse_tiles(input);
+    let counts = count_edges(&tiles);
+    tiles
+        .iter()
+        .filter(|(_, tile)| unmatched_edges(&counts, tile) == 2)
+        .map(|(id, _)| id)
+        .product::<u64>()
+        .to_string()
+}
+
+/// Assembles the tiles into the final image (with the borders removed)
+fn assemble(tiles: &[(u64, Image)]) -> Image {
+    let counts = count_edges(tiles);
+    let side = (tiles.len() as f64).sqrt() as usize;
+    assert_eq!(side * side, tiles.len(), "tiles do not form a square");
+
+    let mut remaining: Vec<Vec<Image>> = tiles.iter().map(|(_, t)| transforms(t)).collect();
+
+    // Orient a corner so its unmatched edges are on the top and left
+    let corner = tiles
+        .iter()
+        .position(|(_, t)| unmatched_edges(&counts, t) == 2)
+        .expect("no corner tile");
+    let first = remaining
+        .swap_remove(corner)
+        .into_iter()
+        .find(|t| {
+            let keys = edge_keys(t);
+            counts[&keys[0]] == 1 && counts[&keys[2]] == 1
+        })
+        .unwrap();
+
+    let mut placed: Vec<Image> = vec![first];
+    for i in 1..tiles.len() {
+        let left = (i % side != 0).then(|| &placed[i - 1]);
+        let above = (i >= side).then(|| &placed[i - side]);
+        let fits = |t: &Image| {
+            left.is_none_or(|l| l.column(l.ncols() - 1) == t.column(0))
+                && above.is_none_or(|a| a.row(a.nrows() - 1) == t.row(0))
+        };
+
+        let (index, tile) = remaining
+            .iter()
+            .enumerate()
+            .find_map(|(index, options)| options.iter().find(|t| fits(t)).map(|t| (index, t)))
+            .expect("no tile fits");
+        let tile = tile.clone();
+        remaining.swap_remove(index);
+        placed.push(tile);
+    }
+
+    // Stitch the tiles together without their borders
+    let inner = placed[0].nrows() - 2;
+    DMatrix::from_fn(side * inner, side * inner, |r, c| {
+        placed[(r / inner) * side + c / inner][(r % inner + 1, c % inner + 1)]
+    })
+}
+
+/// Returns the number of cells which are part of a sea monster
+fn monster_cells(image: &Image) -> usize {
+    let offsets: Vec<(usize, usize)> = MONSTER
+        .iter()
+        .enumerate()
+        .flat_map(|(r, line)| {
+            line.bytes()
+                .enumerate()
+                .filter(|&(_, b)| b == b'#')
+                .map(move |(c, _)| (r, c))
+        })
+        .collect();
+    let (height, width) = (MONSTER.len(), MONSTER[0].len());
+
+    let mut marked = DMatrix::from_element(image.nrows(), image.ncols(), false);
+    for r in 0..=image.nrows().saturating_sub(height) {
+        for c in 0..=image.ncols().saturating_sub(width) {
+            if offsets.iter().all(|&(dr, dc)| image[(r + dr, c + dc)]) {
+                for &(dr, dc) in &offsets {
+                    marked[(r + dr, c + dc)] = true;
+                }
+            }
+        }
+    }
+
+    marked.iter().filter(|&&m| m).count()
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+pub fn star2(input: &str) -> String {
+    let image = assemble(&parse_tiles(input));
+    let monsters = transforms(&image).iter().map(monster_cells).max().unwrap();
+    assert_ne!(monsters, 0, "no sea monsters found");
+    (image.iter().filter(|&&b| b).count() - monsters).to_string()
 }
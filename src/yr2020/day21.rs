@@ -1,7 +1,62 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+type Food<'a> = (HashSet<&'a str>, Vec<&'a str>);
+
+fn parse_foods(input: &str) -> Vec<Food<'_>> {
+    input
+        .lines()
+        .map(|line| {
+            let (ingredients, allergens) = line
+                .trim_end_matches(')')
+                .split_once(" (contains ")
+                .unwrap_or((line, ""));
+            (
+                ingredients.split(' ').collect(),
+                allergens.split(", ").filter(|a| !a.is_empty()).collect(),
+            )
+        })
+        .collect()
+}
+
+/// Returns the set of ingredients which could contain each allergen
+fn candidates<'a>(foods: &[Food<'a>]) -> HashMap<&'a str, HashSet<&'a str>> {
+    let mut result: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for (ingredients, allergens) in foods {
+        for allergen in allergens {
+            result
+                .entry(allergen)
+                .and_modify(|set| set.retain(|i| ingredients.contains(i)))
+                .or_insert_with(|| ingredients.clone());
+        }
+    }
+    result
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+pub fn star1(input: &str) -> String {
+    let foods = parse_foods(input);
+    let unsafe_ingredients: HashSet<_> = candidates(&foods).into_values().flatten().collect();
+    foods
+        .iter()
+        .flat_map(|(ingredients, _)| ingredients)
+        .filter(|i| !unsafe_ingredients.contains(*i))
+        .count()
+        .to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    let mut candidates = candidates(&parse_foods(input));
+    let mut resolved = BTreeMap::new();
+
+    // Repeatedly resolve allergens with only one possible ingredient
+    while let Some((&allergen, set)) = candidates.iter().find(|(_, set)| set.len() == 1) {
+        let ingredient = *set.iter().next().unwrap();
+        resolved.insert(allergen, ingredient);
+        candidates.remove(allergen);
+        for set in candidates.values_mut() {
+            set.remove(ingredient);
+        }
+    }
+
+    assert!(candidates.is_empty(), "allergens cannot be resolved");
+    resolved.into_values().collect::<Vec<_>>().join(",")
 }
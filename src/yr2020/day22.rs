@@ -1,7 +1,80 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use std::collections::{HashMap, HashSet, VecDeque};
+
+type Deck = VecDeque<u8>;
+
+fn parse_decks(input: &str) -> (Deck, Deck) {
+    let (a, b) = input.split_once("\n\n").unwrap();
+    let parse = |s: &str| s.lines().skip(1).map(|l| l.parse().unwrap()).collect();
+    (parse(a), parse(b))
+}
+
+fn score(deck: &Deck) -> usize {
+    deck.iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &c)| (i + 1) * usize::from(c))
+        .sum()
+}
+
+pub fn star1(input: &str) -> String {
+    let (mut a, mut b) = parse_decks(input);
+    while let (Some(&ca), Some(&cb)) = (a.front(), b.front()) {
+        a.pop_front();
+        b.pop_front();
+        if ca > cb {
+            a.extend([ca, cb]);
+        } else {
+            b.extend([cb, ca]);
+        }
+    }
+
+    score(if b.is_empty() { &a } else { &b }).to_string()
+}
+
+/// Plays a game of recursive combat, returning true if player 1 wins
+///  The results of sub-games are cached since the same decks often reappear.
+fn recursive_combat(cache: &mut HashMap<(Deck, Deck), bool>, a: &mut Deck, b: &mut Deck) -> bool {
+    let mut seen = HashSet::new();
+    while let (Some(&ca), Some(&cb)) = (a.front(), b.front()) {
+        if !seen.insert((a.clone(), b.clone())) {
+            return true;
+        }
+
+        a.pop_front();
+        b.pop_front();
+        let a_wins = if a.len() >= usize::from(ca) && b.len() >= usize::from(cb) {
+            let sub_a: Deck = a.iter().take(ca.into()).copied().collect();
+            let sub_b: Deck = b.iter().take(cb.into()).copied().collect();
+            if sub_a.iter().max() > sub_b.iter().max() {
+                // Player 1 can never lose the highest card, so always wins
+                true
+            } else {
+                let key = (sub_a, sub_b);
+                if let Some(&result) = cache.get(&key) {
+                    result
+                } else {
+                    let (mut sub_a, mut sub_b) = key.clone();
+                    let result = recursive_combat(cache, &mut sub_a, &mut sub_b);
+                    cache.insert(key, result);
+                    result
+                }
+            }
+        } else {
+            ca > cb
+        };
+
+        if a_wins {
+            a.extend([ca, cb]);
+        } else {
+            b.extend([cb, ca]);
+        }
+    }
+
+    b.is_empty()
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+pub fn star2(input: &str) -> String {
+    let (mut a, mut b) = parse_decks(input);
+    let a_wins = recursive_combat(&mut HashMap::new(), &mut a, &mut b);
+    score(if a_wins { &a } else { &b }).to_string()
 }
@@ -1,7 +1,74 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+/// Ring of cups stored as a linked list
+///  next[cup] is the label of the cup clockwise of the given cup. Labels
+///  start at 1, so next[0] is unused.
+struct Cups {
+    next: Vec<u32>,
+    current: u32,
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+impl Cups {
+    fn new(input: &str, total: u32) -> Cups {
+        let labels: Vec<u32> = input
+            .bytes()
+            .map(|b| u32::from(b - b'0'))
+            .chain(input.len() as u32 + 1..=total)
+            .collect();
+
+        let mut next = vec![0; labels.len() + 1];
+        for (i, &label) in labels.iter().enumerate() {
+            next[label as usize] = labels[(i + 1) % labels.len()];
+        }
+
+        Cups {
+            next,
+            current: labels[0],
+        }
+    }
+
+    fn play(&mut self, moves: usize) {
+        let max = (self.next.len() - 1) as u32;
+        for _ in 0..moves {
+            // Pick up three cups
+            let a = self.next[self.current as usize];
+            let b = self.next[a as usize];
+            let c = self.next[b as usize];
+            self.next[self.current as usize] = self.next[c as usize];
+
+            // Find destination
+            let mut dest = self.current;
+            loop {
+                dest = if dest == 1 { max } else { dest - 1 };
+                if dest != a && dest != b && dest != c {
+                    break;
+                }
+            }
+
+            // Put the cups back down
+            self.next[c as usize] = self.next[dest as usize];
+            self.next[dest as usize] = a;
+            self.current = self.next[self.current as usize];
+        }
+    }
+
+    /// Iterates over the cups after cup 1
+    fn after_one(&self) -> impl Iterator<Item = u32> + '_ {
+        std::iter::successors(Some(self.next[1]), |&c| Some(self.next[c as usize]))
+            .take_while(|&c| c != 1)
+    }
+}
+
+pub fn star1(input: &str) -> String {
+    let mut cups = Cups::new(input, input.len() as u32);
+    cups.play(100);
+    cups.after_one().map(|c| c.to_string()).collect()
+}
+
+pub fn star2(input: &str) -> String {
+    let mut cups = Cups::new(input, 1_000_000);
+    cups.play(10_000_000);
+    cups.after_one()
+        .take(2)
+        .map(u64::from)
+        .product::<u64>()
+        .to_string()
 }
@@ -1,4 +1,19 @@
 use itertools::Itertools;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::options;
+
+/// Operator packet types and their names in the expression syntax
+const OPERATORS: [(u8, &str); 7] = [
+    (0, "sum"),
+    (1, "product"),
+    (2, "min"),
+    (3, "max"),
+    (5, "gt"),
+    (6, "lt"),
+    (7, "eq"),
+];
 
 #[derive(Clone, Debug)]
 struct BitReader<T> {
@@ -15,6 +30,15 @@ struct BitReader<T> {
     cached_bytes: u32,
 }
 
+/// Writes a stream of bits into bytes, most significant bit first
+#[derive(Clone, Debug, Default)]
+struct BitWriter {
+    data: Vec<u8>,
+
+    /// Number of bits written
+    num_bits: u32,
+}
+
 /// A single transmission packet and its children
 #[derive(Clone, Debug, Eq, PartialEq)]
 enum Packet {
@@ -106,6 +130,44 @@ fn hex_reader(input: &str) -> BitReader<impl Iterator<Item = u8> + '_> {
     )
 }
 
+impl BitWriter {
+    /// Writes a single bit
+    fn write_bit(&mut self, bit: bool) {
+        if self.num_bits.is_multiple_of(8) {
+            self.data.push(0);
+        }
+        if bit {
+            *self.data.last_mut().unwrap() |= 0x80 >> (self.num_bits % 8);
+        }
+        self.num_bits += 1;
+    }
+
+    /// Writes the lowest bits of a value
+    fn write_u32(&mut self, bits: u8, value: u32) {
+        assert!(bits <= 32);
+        assert!(
+            bits == 32 || value >> bits == 0,
+            "{value} does not fit in {bits} bits"
+        );
+
+        for i in (0..bits).rev() {
+            self.write_bit(value >> i & 1 != 0);
+        }
+    }
+
+    /// Writes all the bits from another writer
+    fn append(&mut self, other: &BitWriter) {
+        for i in 0..other.num_bits {
+            self.write_bit(other.data[(i / 8) as usize] & (0x80 >> (i % 8)) != 0);
+        }
+    }
+
+    /// Returns the data written as hex, padded with zeros to a whole byte
+    fn to_hex(&self) -> String {
+        self.data.iter().map(|b| format!("{b:02X}")).join("")
+    }
+}
+
 impl Packet {
     fn parse(reader: &mut BitReader<impl Iterator<Item = u8>>) -> Packet {
         let version = reader.read_u8(3);
@@ -144,6 +206,90 @@ impl Packet {
         }
     }
 
+    /// Writes this packet using the transmission encoding
+    ///  Operators use the bit length encoding for their children when it
+    ///  fits, otherwise they use the packet count.
+    fn encode(&self, writer: &mut BitWriter) {
+        match self {
+            Packet::Literal { version, value } => {
+                writer.write_u32(3, (*version).into());
+                writer.write_u32(3, 4);
+
+                let groups = (64 - value.leading_zeros()).div_ceil(4).max(1);
+                for i in (0..groups).rev() {
+                    writer.write_bit(i != 0);
+                    writer.write_u32(4, (value >> (i * 4) & 0xF) as u32);
+                }
+            }
+            Packet::Operator {
+                version,
+                ty,
+                children,
+            } => {
+                writer.write_u32(3, (*version).into());
+                writer.write_u32(3, (*ty).into());
+
+                let mut child_writer = BitWriter::default();
+                for child in children {
+                    child.encode(&mut child_writer);
+                }
+
+                if child_writer.num_bits < 1 << 15 {
+                    writer.write_bit(false);
+                    writer.write_u32(15, child_writer.num_bits);
+                } else {
+                    writer.write_bit(true);
+                    writer.write_u32(11, children.len() as u32);
+                }
+                writer.append(&child_writer);
+            }
+        }
+    }
+
+    /// Encodes this packet as a hex transmission
+    fn to_hex(&self) -> String {
+        let mut writer = BitWriter::default();
+        self.encode(&mut writer);
+        writer.to_hex()
+    }
+
+    /// Returns the name of an operator type
+    fn operator_name(ty: u8) -> &'static str {
+        OPERATORS
+            .iter()
+            .find(|&&(t, _)| t == ty)
+            .map_or("unknown", |&(_, name)| name)
+    }
+
+    /// Writes an indented tree of this packet showing versions and values
+    fn pretty(&self, f: &mut impl fmt::Write, depth: usize) -> fmt::Result {
+        let indent = depth * 2;
+        match self {
+            Packet::Literal { version, value } => {
+                writeln!(f, "{:indent$}{value} (v{version})", "")
+            }
+            Packet::Operator {
+                version,
+                ty,
+                children,
+            } => {
+                let name = Packet::operator_name(*ty);
+                writeln!(f, "{:indent$}{name} (v{version}) = {}", "", self.evaluate())?;
+                for child in children {
+                    child.pretty(f, depth + 1)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the indented tree form of this packet
+    fn pretty_string(&self) -> String {
+        let mut result = String::new();
+        self.pretty(&mut result, 0).unwrap();
+        result
+    }
+
     fn sum_versions(&self) -> u32 {
         match self {
             Packet::Literal { version, .. } => *version as u32,
@@ -173,12 +319,238 @@ impl Packet {
     }
 }
 
+/// Displays the packet in the expression syntax (without versions)
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Packet::Literal { value, .. } => write!(f, "{value}"),
+            Packet::Operator { ty, children, .. } => {
+                write!(
+                    f,
+                    "{}({})",
+                    Packet::operator_name(*ty),
+                    children.iter().join(", ")
+                )
+            }
+        }
+    }
+}
+
+/// An error encountered while parsing an expression
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct ParseError {
+    /// Byte offset of the error in the input
+    position: usize,
+    message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+/// Recursive descent parser for the expression syntax
+///  Expressions are either integer literals or an operator name followed by
+///  a bracketed list of arguments. All packets have version 0.
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    /// Creates an error at the current position
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, ParseError> {
+        Err(ParseError {
+            position: self.pos,
+            message: message.into(),
+        })
+    }
+
+    /// Skips whitespace and returns the remaining input
+    fn rest(&mut self) -> &str {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+        &self.input[self.pos..]
+    }
+
+    /// Consumes characters while the predicate holds and returns them
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &str {
+        let rest = self.rest();
+        let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        let start = self.pos;
+        self.pos += len;
+        &self.input[start..self.pos]
+    }
+
+    /// Consumes the given character or returns an error
+    fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        if self.rest().starts_with(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            self.error(format!("expected '{c}'"))
+        }
+    }
+
+    /// Parses a packet expression
+    fn packet(&mut self) -> Result<Packet, ParseError> {
+        let start = self.pos;
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        if !digits.is_empty() {
+            if let Ok(value) = digits.parse() {
+                return Ok(Packet::Literal { version: 0, value });
+            }
+            self.pos = start;
+            return self.error("literal too large");
+        }
+
+        let name = self.take_while(|c| c.is_ascii_alphabetic());
+        let Some(&(ty, _)) = OPERATORS.iter().find(|(_, n)| *n == name) else {
+            self.pos = start;
+            return self.error("expected literal or operator");
+        };
+
+        self.expect('(')?;
+        let mut children = vec![self.packet()?];
+        while self.rest().starts_with(',') {
+            self.pos += 1;
+            children.push(self.packet()?);
+        }
+        self.expect(')')?;
+
+        if (5..=7).contains(&ty) && children.len() != 2 {
+            self.pos = start;
+            return self.error("comparisons need exactly 2 arguments");
+        }
+
+        Ok(Packet::Operator {
+            version: 0,
+            ty,
+            children,
+        })
+    }
+}
+
+impl FromStr for Packet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Packet, ParseError> {
+        let mut parser = Parser { input: s, pos: 0 };
+        let packet = parser.packet()?;
+        if !parser.rest().is_empty() {
+            return parser.error("trailing characters");
+        }
+        Ok(packet)
+    }
+}
+
+/// Reads a packet from a hex transmission or from the expression syntax
+///  Expressions are encoded into a transmission and decoded again, so they
+///  can be used to generate test transmissions. Inputs which are entirely
+///  hex digits are always treated as transmissions. In verbose mode, the
+///  generated transmission is printed.
+fn read_packet(input: &str) -> Packet {
+    let input = input.trim();
+    if input.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Packet::parse(&mut hex_reader(input));
+    }
+
+    let packet: Packet = input
+        .parse()
+        .unwrap_or_else(|e| panic!("invalid expression: {e}"));
+    let hex = packet.to_hex();
+    if options::verbose() {
+        eprintln!("{hex}");
+    }
+    Packet::parse(&mut hex_reader(&hex))
+}
+
 pub fn star1(input: &str) -> String {
-    Packet::parse(&mut hex_reader(input))
-        .sum_versions()
-        .to_string()
+    read_packet(input).sum_versions().to_string()
 }
 
+/// Evaluates the packet, printing its tree in verbose mode
 pub fn star2(input: &str) -> String {
-    Packet::parse(&mut hex_reader(input)).evaluate().to_string()
+    let packet = read_packet(input);
+    if options::verbose() {
+        eprint!("{}", packet.pretty_string());
+    }
+    packet.evaluate().to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLES: [&str; 8] = [
+        "D2FE28",
+        "38006F45291200",
+        "EE00D40C823060",
+        "8A004A801A8002F478",
+        "620080001611562C8802118E34",
+        "C0015000016115A2E0802F182340",
+        "A0016C880162017C3686B18A3D4780",
+        "9C0141080250320F1802104A08",
+    ];
+
+    fn decode(hex: &str) -> Packet {
+        Packet::parse(&mut hex_reader(hex))
+    }
+
+    #[test]
+    fn round_trip() {
+        for hex in EXAMPLES {
+            let packet = decode(hex);
+            assert_eq!(decode(&packet.to_hex()), packet);
+        }
+    }
+
+    #[test]
+    fn count_encoding() {
+        // Enough children that the bit length does not fit in 15 bits
+        let packet = Packet::Operator {
+            version: 5,
+            ty: 0,
+            children: vec![
+                Packet::Literal {
+                    version: 7,
+                    value: u64::MAX,
+                };
+                500
+            ],
+        };
+        assert_eq!(decode(&packet.to_hex()), packet);
+    }
+
+    #[test]
+    fn expressions() {
+        let packet: Packet = "sum(1, product(2, 3), max(0, 17))".parse().unwrap();
+        assert_eq!(packet.evaluate(), 24);
+        assert_eq!(packet.to_string(), "sum(1, product(2, 3), max(0, 17))");
+        assert_eq!(decode(&packet.to_hex()), packet);
+
+        assert_eq!(
+            decode("9C0141080250320F1802104A08").to_string(),
+            "eq(sum(1, 3), product(2, 2))"
+        );
+    }
+
+    #[test]
+    fn expression_errors() {
+        let position = |s: &str| s.parse::<Packet>().unwrap_err().position;
+        assert_eq!(position("sum(1, 2"), 8);
+        assert_eq!(position("add(1)"), 0);
+        assert_eq!(position("gt(1, 2, 3)"), 0);
+        assert_eq!(position("min() "), 4);
+        assert_eq!(position("1 2"), 2);
+    }
+
+    #[test]
+    fn pretty() {
+        assert_eq!(
+            decode("C200B40A82").pretty_string(),
+            "sum (v6) = 3\n  1 (v6)\n  2 (v2)\n"
+        );
+    }
 }
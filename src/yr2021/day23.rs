@@ -1,225 +1,398 @@
-use itertools::Itertools;
-use std::collections::{BinaryHeap, HashSet};
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
 
-/// Describes a type of bug (A, B, C or D)
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-struct BugType(u8);
+use crate::options;
 
-/// Describes a position in the hallway
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-struct Hallway(u8);
+/// Contents of an empty cell
+const EMPTY: u8 = b'.';
 
-/// Describes a bug starting position
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-struct StartPos(u8);
+/// Contents of each cell of a burrow, using the same characters as the diagram
+type State = Vec<u8>;
 
-type StartState = Vec<(BugType, StartPos)>;
-type HallwayState = Vec<(BugType, Hallway)>;
+/// A room which one type of amphipod must end up in
+#[derive(Clone, Debug)]
+struct Room {
+    /// Hallway cell just outside the room
+    entrance: usize,
+    /// Cells in the room from the top down
+    cells: Vec<usize>,
+}
 
-impl BugType {
-    /// Enumerate all types of bug
-    fn all_types() -> impl Iterator<Item = BugType> {
-        (0..4).map(BugType)
-    }
+/// The layout of a burrow
+///  Cells are numbered with the hallway from left to right first, followed
+///  by the cells of each room. Room i belongs to amphipod type i, written as
+///  the i-th letter of the alphabet.
+#[derive(Clone, Debug)]
+struct Burrow {
+    /// Original diagram, used to redraw states
+    diagram: Vec<Vec<u8>>,
+    /// (row, column) of each cell in the diagram
+    positions: Vec<(usize, usize)>,
+    /// Number of hallway cells
+    hallway: usize,
+    rooms: Vec<Room>,
+    /// Energy used by each type of amphipod per step
+    costs: Vec<u32>,
+}
 
-    /// Calculates the cost per move of this bug type
-    fn cost(self) -> i32 {
-        10_i32.pow(self.0.into())
-    }
+impl Burrow {
+    /// Parses a burrow diagram and returns the burrow and its initial state
+    ///  The hallway is the first row containing open cells, and rooms are
+    ///  columns of open cells below it. An optional line "Costs: 1 10 ..."
+    ///  after the diagram sets the energy per step of each type, which
+    ///  otherwise increases by a factor of 10 for each type.
+    fn parse(input: &str) -> (Burrow, State) {
+        let is_open = |c: u8| c == EMPTY || c.is_ascii_uppercase();
+        let mut diagram = Vec::new();
+        let mut costs = None;
+        for line in input.lines().filter(|l| !l.trim().is_empty()) {
+            if let Some(rest) = line.trim().strip_prefix("Costs:") {
+                costs = Some(
+                    rest.split_whitespace()
+                        .map(|c| c.parse().unwrap())
+                        .collect::<Vec<u32>>(),
+                );
+            } else {
+                diagram.push(line.as_bytes().to_vec());
+            }
+        }
+
+        let hallway_row = diagram
+            .iter()
+            .position(|row| row.iter().copied().any(is_open))
+            .expect("burrow has no hallway");
+        let mut positions: Vec<(usize, usize)> = (0..diagram[hallway_row].len())
+            .filter(|&col| is_open(diagram[hallway_row][col]))
+            .map(|col| (hallway_row, col))
+            .collect();
+        let hallway = positions.len();
+        let first_col = positions[0].1;
+        assert!(
+            positions
+                .iter()
+                .enumerate()
+                .all(|(i, p)| p.1 == first_col + i),
+            "hallway must be a single line of cells"
+        );
+
+        let mut rooms: Vec<Room> = Vec::new();
+        for col in first_col..first_col + hallway {
+            let cells: Vec<usize> = (hallway_row + 1..diagram.len())
+                .take_while(|&row| diagram[row].get(col).is_some_and(|&c| is_open(c)))
+                .map(|row| {
+                    positions.push((row, col));
+                    positions.len() - 1
+                })
+                .collect();
+
+            if !cells.is_empty() {
+                rooms.push(Room {
+                    entrance: col - first_col,
+                    cells,
+                });
+            }
+        }
 
-    /// Returns the hallway index this bug is destined for
-    fn goal(self) -> Hallway {
-        Hallway((self.0 + 1) * 2)
+        let state: State = positions.iter().map(|&(r, c)| diagram[r][c]).collect();
+        for &c in &state {
+            assert!(
+                c == EMPTY || usize::from(c - b'A') < rooms.len(),
+                "amphipod {} has no room",
+                char::from(c)
+            );
+        }
+
+        let costs =
+            costs.unwrap_or_else(|| (0..rooms.len()).map(|i| 10u32.pow(i as u32)).collect());
+        assert!(costs.len() >= rooms.len(), "not enough costs");
+
+        let burrow = Burrow {
+            diagram,
+            positions,
+            hallway,
+            rooms,
+            costs,
+        };
+        (burrow, state)
     }
-}
 
-impl Hallway {
-    /// Enumerate all valid hallway destinations
-    fn all_destinations() -> impl Iterator<Item = Hallway> {
-        [0, 1, 3, 5, 7, 9, 10].into_iter().map(Hallway)
+    /// Returns the room index an amphipod belongs in
+    fn room_of(amphipod: u8) -> usize {
+        usize::from(amphipod - b'A')
     }
 
-    /// Would this hallway block a move from src to dst?
-    fn would_block(self, src: Hallway, dst: Hallway) -> bool {
-        if src < dst {
-            self > src && self <= dst
-        } else {
-            self >= dst && self < src
-        }
+    /// Returns true if a room contains no amphipods of other types
+    fn accepts(&self, state: &State, room: usize) -> bool {
+        let own = b'A' + room as u8;
+        self.rooms[room]
+            .cells
+            .iter()
+            .all(|&c| state[c] == EMPTY || state[c] == own)
     }
 
-    /// Computes the absolute distance between two hallways
-    fn abs_diff(self, other: Hallway) -> u8 {
-        if self < other {
-            other.0 - self.0
+    /// Returns true if all the hallway cells between two positions are empty
+    ///  The start position is excluded and the end position is included.
+    fn hallway_clear(state: &State, from: usize, to: usize) -> bool {
+        let range = if from < to {
+            from + 1..=to
         } else {
-            self.0 - other.0
-        }
+            to..=from - 1
+        };
+        range.into_iter().all(|h| state[h] == EMPTY)
     }
 
-    /// Computes the penalty for a bug to move to this hallway one way
-    fn penalty(self, src: Hallway, bt: BugType) -> i32 {
-        let left = src.0.min(bt.goal().0);
-        let right = src.0.max(bt.goal().0);
+    /// Returns true if every amphipod is in its own room
+    fn is_complete(&self, state: &State) -> bool {
+        state[..self.hallway].iter().all(|&c| c == EMPTY)
+            && (0..self.rooms.len()).all(|r| self.accepts(state, r))
+    }
 
-        if self.0 < left {
-            (left - self.0) as i32 * bt.cost()
-        } else if self.0 > right {
-            (self.0 - right) as i32 * bt.cost()
-        } else {
-            0
+    /// Returns a lower bound on the energy needed to finish from a state
+    ///  Each amphipod which is not settled at the bottom of its own room must
+    ///  at least walk to the top cell of its room.
+    fn heuristic(&self, state: &State) -> u32 {
+        let mut total = 0;
+        for (h, &c) in state[..self.hallway].iter().enumerate() {
+            if c != EMPTY {
+                let target = self.rooms[Burrow::room_of(c)].entrance;
+                total += (h.abs_diff(target) as u32 + 1) * self.costs[Burrow::room_of(c)];
+            }
         }
-    }
-}
 
-impl StartPos {
-    /// Constructs a starting position from a room and row number
-    fn from_room_row(room: u8, row: u8) -> StartPos {
-        StartPos(room + row * 4)
-    }
+        for (r, room) in self.rooms.iter().enumerate() {
+            for (depth, &cell) in room.cells.iter().enumerate() {
+                let c = state[cell];
+                if c == EMPTY {
+                    continue;
+                }
 
-    /// Returns the room id of this start position
-    fn room(self) -> u8 {
-        self.0 % 4
-    }
+                let target = Burrow::room_of(c);
+                let steps = if target != r {
+                    depth + 1 + room.entrance.abs_diff(self.rooms[target].entrance) + 1
+                } else if room.cells[depth..].iter().any(|&below| state[below] != c) {
+                    // Out, aside, back and in again
+                    depth + 4
+                } else {
+                    0
+                };
+                total += steps as u32 * self.costs[target];
+            }
+        }
 
-    /// Returns the row of this start position
-    fn row(self) -> u8 {
-        self.0 / 4
+        total
     }
 
-    /// Returns the hallway this bug would enter when leaving the room
-    fn entering_hallway(self) -> Hallway {
-        BugType(self.room()).goal()
-    }
-}
+    /// Returns all states reachable in one move, with the energy used
+    fn moves(&self, state: &State) -> Vec<(State, u32)> {
+        let mut result = Vec::new();
+        let mut push = |from: usize, to: usize, steps: usize| {
+            let mut next = state.clone();
+            next[to] = state[from];
+            next[from] = EMPTY;
+            let cost = steps as u32 * self.costs[Burrow::room_of(state[from])];
+            result.push((next, cost));
+        };
+
+        // Returns the deepest free cell of the amphipod's room if it can enter it
+        let destination = |amphipod: u8| {
+            let target = Burrow::room_of(amphipod);
+            if self.accepts(state, target) {
+                let room = &self.rooms[target];
+                room.cells
+                    .iter()
+                    .rposition(|&c| state[c] == EMPTY)
+                    .map(|depth| (room, depth))
+            } else {
+                None
+            }
+        };
 
-/// Remove bugs from the start state which are already in the correct position
-fn remove_complete_bugs(state: &mut StartState, depth: u8) {
-    for bug_type in BugType::all_types() {
-        for row in (0..depth).rev() {
-            if let Some(i) = state
-                .iter()
-                .position(|&b| b == (bug_type, StartPos::from_room_row(bug_type.0, row)))
+        // Amphipods in the hallway can only move into their own room
+        for h in 0..self.hallway {
+            if state[h] != EMPTY
+                && let Some((room, depth)) = destination(state[h])
+                && Burrow::hallway_clear(state, h, room.entrance)
             {
-                state.swap_remove(i);
-            } else {
-                break;
+                push(h, room.cells[depth], h.abs_diff(room.entrance) + depth + 1);
             }
         }
-    }
-}
 
-fn star_common(mut initial_state: StartState) -> String {
-    assert_eq!(initial_state.len() % 4, 0);
-    let depth = (initial_state.len() / 4) as u8;
-    remove_complete_bugs(&mut initial_state, depth);
-    initial_state.sort_unstable();
-
-    // Calculate "perfect" cost (all bugs go directly to their
-    // destinations). This is made up of the cost to move each bug
-    // from it's start position into its room plus the cost of moving
-    // down to the final position within a room.
-    let cost1: i32 = initial_state
-        .iter()
-        .map(|(bt, pos)| {
-            (pos.row() + 1 + pos.entering_hallway().abs_diff(bt.goal())) as i32 * bt.cost()
-        })
-        .sum();
-    let cost2: i32 = initial_state
-        .iter()
-        .counts_by(|(bt, _)| bt)
-        .into_iter()
-        .map(|(bt, n)| (n * (n + 1) / 2) as i32 * bt.cost())
-        .sum();
-    let perfect_cost = cost1 + cost2;
-
-    // All states in either list are guaranteed to be relaxed
-    let mut open = BinaryHeap::new();
-    let mut closed = HashSet::new();
-    open.push((0, initial_state, HallwayState::new()));
-
-    // Find path with the lowest penalty from the perfect cost
-    while let Some((penalty, start_state, hallway_state)) = open.pop() {
-        if closed.insert((start_state.clone(), hallway_state.clone())) {
-            if start_state.is_empty() && hallway_state.is_empty() {
-                return (perfect_cost - penalty).to_string();
+        // The top amphipod of a room moves out unless the room is finished
+        for (r, room) in self.rooms.iter().enumerate() {
+            if self.accepts(state, r) {
+                continue;
             }
 
-            for (i, &(bt, p)) in start_state.iter().enumerate() {
-                // Is this bug blocked from leaving?
-                if start_state
-                    .iter()
-                    .any(|(_, p2)| p.room() == p2.room() && p.row() > p2.row())
+            let depth = room.cells.iter().position(|&c| state[c] != EMPTY).unwrap();
+            let from = room.cells[depth];
+            if state[room.entrance] != EMPTY {
+                continue;
+            }
+
+            if let Some((target, target_depth)) = destination(state[from])
+                && Burrow::hallway_clear(state, room.entrance, target.entrance)
+            {
+                let steps = depth + 1 + room.entrance.abs_diff(target.entrance) + target_depth + 1;
+                push(from, target.cells[target_depth], steps);
+            }
+
+            for h in 0..self.hallway {
+                if self.rooms.iter().all(|other| other.entrance != h)
+                    && Burrow::hallway_clear(state, room.entrance, h)
                 {
-                    continue;
+                    push(from, h, depth + 1 + h.abs_diff(room.entrance));
                 }
+            }
+        }
 
-                // Enumerate all hallway moves this bug could make
-                for dst in Hallway::all_destinations() {
-                    // Are we blocked from getting here?
-                    if hallway_state
-                        .iter()
-                        .any(|(_, h)| h.would_block(p.entering_hallway(), dst))
-                    {
-                        continue;
-                    }
+        result
+    }
 
-                    // Compute new state
-                    let new_penalty = penalty - 2 * dst.penalty(p.entering_hallway(), bt);
-                    let mut new_start_state = start_state.clone();
-                    new_start_state.remove(i);
-                    let mut new_hallway_state = hallway_state.clone();
-                    new_hallway_state.push((bt, dst));
-
-                    // Remove bugs in hallways which can move to their
-                    // destinations immediately
-                    while let Some(i) = new_hallway_state.iter().position(|&(bt, h)| {
-                        let hallway_block = new_hallway_state
-                            .iter()
-                            .any(|(_, h2)| h2.would_block(h, bt.goal()));
-                        let enter_block = new_start_state
-                            .iter()
-                            .any(|(_, s)| s.entering_hallway() == bt.goal());
-
-                        !hallway_block && !enter_block
-                    }) {
-                        new_hallway_state.swap_remove(i);
-                    }
+    /// Finds the cheapest way to move every amphipod into its own room
+    ///  Returns each state along the way with the total energy used so far.
+    fn solve(&self, initial: State) -> Option<Vec<(State, u32)>> {
+        let mut best: HashMap<State, (u32, Option<State>)> = HashMap::new();
+        let mut open = BinaryHeap::new();
+        best.insert(initial.clone(), (0, None));
+        open.push(Reverse((self.heuristic(&initial), 0, initial)));
+
+        while let Some(Reverse((_, cost, state))) = open.pop() {
+            if best[&state].0 < cost {
+                continue;
+            }
 
-                    new_hallway_state.sort_unstable();
+            if self.is_complete(&state) {
+                let mut path = vec![(state, cost)];
+                while let Some(previous) = &best[&path.last().unwrap().0].1 {
+                    path.push((previous.clone(), best[previous].0));
+                }
+                path.reverse();
+                return Some(path);
+            }
 
-                    // Relax before pushing
-                    open.push((new_penalty, new_start_state, new_hallway_state));
+            for (next, step_cost) in self.moves(&state) {
+                let next_cost = cost + step_cost;
+                match best.entry(next.clone()) {
+                    Entry::Occupied(e) if e.get().0 <= next_cost => continue,
+                    Entry::Occupied(mut e) => *e.get_mut() = (next_cost, Some(state.clone())),
+                    Entry::Vacant(e) => {
+                        e.insert((next_cost, Some(state.clone())));
+                    }
                 }
+                open.push(Reverse((
+                    next_cost + self.heuristic(&next),
+                    next_cost,
+                    next,
+                )));
             }
         }
+
+        None
     }
 
-    panic!("no way to reach final state")
+    /// Redraws the diagram with the amphipods in the given state
+    fn draw(&self, state: &State) -> String {
+        let mut diagram = self.diagram.clone();
+        for (&(row, col), &c) in self.positions.iter().zip(state) {
+            diagram[row][col] = c;
+        }
+
+        diagram
+            .into_iter()
+            .map(|row| String::from_utf8(row).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
-fn parse_start_pos((i, c): (usize, char)) -> (BugType, StartPos) {
-    (BugType(c as u8 - b'A'), StartPos(i as u8))
+/// Returns the least energy needed to organise the amphipods in a diagram
+///  In verbose mode, the diagram is printed after each move of the solution.
+fn star_common(input: &str) -> String {
+    let (burrow, initial) = Burrow::parse(input);
+    let path = burrow
+        .solve(initial)
+        .expect("no way to organise the amphipods");
+
+    if options::verbose() {
+        for (state, cost) in &path {
+            eprintln!("Energy {cost}\n{}\n", burrow.draw(state));
+        }
+    }
+
+    path.last().unwrap().1.to_string()
 }
 
 pub fn star1(input: &str) -> String {
-    star_common(
-        input
-            .chars()
-            .filter(|c| ('A'..='D').contains(c))
-            .enumerate()
-            .map(parse_start_pos)
-            .collect(),
-    )
+    star_common(input)
 }
 
+/// Unfolds the diagram by inserting two extra rows below the top row of
+/// the rooms (these rows assume the puzzle's four rooms)
 pub fn star2(input: &str) -> String {
-    let in_chars = input.chars().filter(|c| ('A'..='D').contains(c));
-    let spliced_chars = in_chars
-        .clone()
-        .take(4)
-        .chain("DCBADBAC".chars())
-        .chain(in_chars.clone().skip(4));
-    star_common(spliced_chars.enumerate().map(parse_start_pos).collect())
+    let mut lines: Vec<&str> = input.lines().collect();
+    let insert_at = lines
+        .iter()
+        .position(|l| l.bytes().any(|c| c.is_ascii_uppercase()))
+        .unwrap()
+        + 1;
+    lines.splice(insert_at..insert_at, ["  #D#C#B#A#", "  #D#B#A#C#"]);
+    star_common(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SMALL: &str = "#######\n#.....#\n###B#A#\n  #####";
+
+    #[test]
+    fn small_burrow() {
+        let (burrow, initial) = Burrow::parse(SMALL);
+        assert_eq!(burrow.hallway, 5);
+        assert_eq!(burrow.rooms.len(), 2);
+        assert_eq!(burrow.rooms[1].entrance, 4);
+
+        let path = burrow.solve(initial).unwrap();
+        assert_eq!(path.last().unwrap().1, 46);
+        assert_eq!(burrow.draw(&path[0].0), SMALL);
+
+        // A must wait in the hallway, and B cannot reach its room directly
+        assert_eq!(path[1].1, 4);
+        assert_eq!(
+            burrow.draw(&path[1].0),
+            "#######\n#.A...#\n###B#.#\n  #####"
+        );
+        assert_eq!(
+            burrow.draw(&path[2].0),
+            "#######\n#.A.B.#\n###.#.#\n  #####"
+        );
+        assert_eq!(
+            burrow.draw(&path.last().unwrap().0),
+            "#######\n#.....#\n###A#B#\n  #####"
+        );
+    }
+
+    #[test]
+    fn costs() {
+        assert_eq!(star1(&format!("{SMALL}\nCosts: 1 1")), "10");
+        assert_eq!(star1(&format!("{SMALL}\nCosts: 5 1")), "26");
+    }
+
+    #[test]
+    fn more_rooms() {
+        let diagram = "\
+###############
+#.............#
+###B#C#A#E#D###
+  #A#B#C#D#E#
+  #A#B#C#D#E#
+  ###########";
+        let (burrow, initial) = Burrow::parse(diagram);
+        assert_eq!(burrow.rooms.len(), 5);
+        let path = burrow.solve(initial).unwrap();
+
+        // A waits outside rooms A to C, and D waits right of room E
+        assert_eq!(path.last().unwrap().1, 448 + 46000);
+    }
 }
@@ -3,6 +3,8 @@ use std::collections::{HashMap, VecDeque};
 use itertools::Itertools;
 use nalgebra::DMatrix;
 
+use crate::search::{BranchAndBound, Problem};
+
 #[derive(Clone, Debug, Default)]
 struct Valve {
     rate: u32,
@@ -84,72 +86,110 @@ fn pathfinding(valves: &[Valve]) -> (Vec<u32>, DMatrix<u32>) {
     )
 }
 
-/// Runs a DFS with the given start point and returns the best pressure release
-///  Will not attempt to open valve at "start". Score does not include opened valves.
-fn dfs(
-    rates: &[u32],
-    costs: &DMatrix<u32>,
-    opened: &mut Vec<usize>,
-    start: usize,
+/// The valves with non-zero flow rates and the travel times between them
+///  Valve 0 is the starting valve, which is never opened.
+struct Tunnels {
+    rates: Vec<u32>,
+    costs: DMatrix<u32>,
+}
+
+/// A position in the search, with the set of opened valves stored as a bitmask
+///  Bit i in the mask represents valve i + 1.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct State {
+    position: usize,
+    opened: u32,
     time_left: u32,
-) -> u32 {
-    let mut best = 0;
-
-    if time_left > 1 {
-        for (vid, &rate) in rates.iter().enumerate() {
-            if vid != 0
-                && vid != start
-                && let Some(new_time_left) = time_left.checked_sub(costs[(start, vid)] + 1)
-                && new_time_left > 0
-                && !opened.contains(&vid)
-            {
-                opened.push(vid);
-                best =
-                    best.max(rate * new_time_left + dfs(rates, costs, opened, vid, new_time_left));
-                opened.pop();
-            }
-        }
+}
+
+impl Tunnels {
+    fn parse(input: &str) -> Tunnels {
+        let (rates, costs) = pathfinding(&parse_input(input));
+        assert!(rates.len() <= 32, "too many valves");
+        Tunnels { rates, costs }
     }
 
-    best
-}
+    /// Returns the time left after travelling to and opening a valve
+    fn open_time(&self, state: &State, valve: usize) -> Option<u32> {
+        state
+            .time_left
+            .checked_sub(self.costs[(state.position, valve)] + 1)
+            .filter(|&t| t > 0)
+    }
 
-pub fn star1(input: &str) -> String {
-    let (rates, costs) = pathfinding(&parse_input(input));
-    dfs(&rates, &costs, &mut Vec::new(), 0, 30).to_string()
-}
+    /// Upper bound on the extra pressure released by travelling directly to
+    /// every unopened valve
+    fn direct_bound(&self, state: &State) -> u32 {
+        (1..self.rates.len())
+            .filter(|v| state.opened & (1 << (v - 1)) == 0)
+            .filter_map(|v| Some(self.rates[v] * self.open_time(state, v)?))
+            .sum()
+    }
 
-fn generate_opened(opened: &mut Vec<usize>, rates_len: usize, i: usize) {
-    opened.clear();
-    for j in 0..(rates_len - 1) {
-        if i & (1 << j) != 0 {
-            opened.push(j + 1);
+    fn initial(time_left: u32) -> State {
+        State {
+            position: 0,
+            opened: 0,
+            time_left,
         }
     }
 }
 
+impl Problem for Tunnels {
+    type State = State;
+
+    fn successors(&self, state: &State) -> Vec<(State, u32)> {
+        (1..self.rates.len())
+            .filter(|v| state.opened & (1 << (v - 1)) == 0)
+            .filter_map(|v| {
+                let time_left = self.open_time(state, v)?;
+                let next = State {
+                    position: v,
+                    opened: state.opened | (1 << (v - 1)),
+                    time_left,
+                };
+                Some((next, self.rates[v] * time_left))
+            })
+            .collect()
+    }
+}
+
+pub fn star1(input: &str) -> String {
+    let tunnels = Tunnels::parse(input);
+    BranchAndBound::new(&tunnels)
+        .bound(|state| tunnels.direct_bound(state))
+        .memoise()
+        .solve(Tunnels::initial(30))
+        .to_string()
+}
+
 pub fn star2(input: &str) -> String {
-    let (rates, costs) = pathfinding(&parse_input(input));
-    let mut opened = Vec::with_capacity(rates.len());
-    let mut best = 0;
-
-    for i in 0..(1 << (rates.len() - 2)) {
-        generate_opened(&mut opened, rates.len(), i);
-        let score_a = dfs(&rates, &costs, &mut opened, 0, 26);
-
-        generate_opened(&mut opened, rates.len(), !i);
-        let upper_bound = rates
-            .iter()
-            .enumerate()
-            .filter(|(i, _)| !opened.contains(i))
-            .map(|(_, r)| r)
-            .sum::<u32>()
-            * 24;
-        if score_a + upper_bound > best {
-            let score_b = dfs(&rates, &costs, &mut opened, 0, 26);
-            best = best.max(score_a + score_b);
+    let tunnels = Tunnels::parse(input);
+    let valves = tunnels.rates.len() - 1;
+
+    // Best score for each set of opened valves
+    let mut best = vec![0; 1 << valves];
+    BranchAndBound::new(&tunnels)
+        .memoise()
+        .explore(Tunnels::initial(26), |state, score| {
+            let entry = &mut best[state.opened as usize];
+            *entry = (*entry).max(score);
+        });
+
+    // Extend to the best score using any subset of each set
+    for bit in 0..valves {
+        for mask in 0..best.len() {
+            if mask & (1 << bit) != 0 {
+                best[mask] = best[mask].max(best[mask ^ (1 << bit)]);
+            }
         }
     }
 
-    best.to_string()
+    // The elephant opens valves from the complement of the player's set
+    let all = best.len() - 1;
+    (0..best.len())
+        .map(|mask| best[mask] + best[all ^ mask])
+        .max()
+        .unwrap()
+        .to_string()
 }
@@ -57,7 +57,7 @@ fn drop_rock(rows: &mut Vec<u8>, jet: &mut impl Iterator<Item = (usize, bool)>,
     }
 
     // Ossify the rock
-    for (row, rock_value) in rows[row..].iter_mut().zip(rock.0.to_le_bytes().into_iter()) {
+    for (row, rock_value) in rows[row..].iter_mut().zip(rock.0.to_le_bytes()) {
         *row |= rock_value;
     }
 
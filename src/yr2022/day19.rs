@@ -1,75 +1,161 @@
 use itertools::Itertools;
-use nalgebra::Vector4;
-use num::Zero;
+use nalgebra::Vector3;
+use std::thread;
 
-fn solve_blueprint<'a>(parts_iter: impl Iterator<Item = &'a str>, initial_time: i32) -> i32 {
-    let parts: Vec<_> = parts_iter
-        .enumerate()
-        .filter(|(i, _)| [1, 6, 12, 18, 21, 27, 30].contains(i))
-        .map(|(_, p)| p.trim_matches(':').parse().unwrap())
-        .collect();
-
-    let costs = [
-        Vector4::new(parts[1], 0, 0, 0),
-        Vector4::new(parts[2], 0, 0, 0),
-        Vector4::new(parts[3], parts[4], 0, 0),
-        Vector4::new(parts[5], 0, parts[6], 0),
-    ];
-
-    let mut best_geodes = 0;
-    let mut states = vec![(Vector4::new(1, 0, 0, 0), Vector4::zero(), initial_time)];
-
-    while let Some((robots, resources, time)) = states.pop() {
-        // Test no more robots
-        let idle_geodes = resources[3] + robots[3] * time;
-        best_geodes = best_geodes.max(idle_geodes);
-
-        // Check geodes upper bound
-        if best_geodes >= idle_geodes + time * (time - 1) / 2 {
-            continue;
+use crate::options;
+use crate::search::{BranchAndBound, Problem};
+
+/// Robot costs in ore, clay and obsidian
+///  Robots are indexed ore, clay, obsidian, geode.
+struct Blueprint {
+    costs: [Vector3<i32>; 4],
+    max_robots: Vector3<i32>,
+}
+
+/// Robots and resources at some time
+///  Geode robots are not stored: the geodes each one will crack by the end
+///  are counted as score when it is built.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct State {
+    robots: Vector3<i32>,
+    resources: Vector3<i32>,
+    time: i32,
+}
+
+impl Blueprint {
+    fn parse<'a>(parts_iter: impl Iterator<Item = &'a str>) -> Blueprint {
+        let parts: Vec<i32> = parts_iter
+            .enumerate()
+            .filter(|(i, _)| [1, 6, 12, 18, 21, 27, 30].contains(i))
+            .map(|(_, p)| p.trim_matches(':').parse().unwrap())
+            .collect();
+
+        let costs = [
+            Vector3::new(parts[1], 0, 0),
+            Vector3::new(parts[2], 0, 0),
+            Vector3::new(parts[3], parts[4], 0),
+            Vector3::new(parts[5], 0, parts[6]),
+        ];
+
+        // We never need more robots than can be spent on a single robot each minute
+        let max_robots = costs.iter().fold(Vector3::zeros(), |a, c| a.sup(c));
+        Blueprint { costs, max_robots }
+    }
+
+    /// Upper bound on extra geodes assuming a geode robot is built every minute
+    fn geode_bound(state: &State) -> u32 {
+        (state.time * (state.time - 1) / 2) as u32
+    }
+
+    /// Finds the largest number of geodes which can be opened
+    ///  With a beam width, the result may be less than the largest number.
+    fn solve(&self, time: i32, beam_width: Option<usize>) -> u32 {
+        let initial = State {
+            robots: Vector3::new(1, 0, 0),
+            resources: Vector3::zeros(),
+            time,
+        };
+
+        let mut search = BranchAndBound::new(self).bound(Blueprint::geode_bound);
+        if let Some(width) = beam_width {
+            search = search.beam_width(width);
         }
+        search.solve(initial)
+    }
+}
+
+impl Problem for Blueprint {
+    type State = State;
 
-        for (i, cost) in costs.iter().enumerate() {
-            /* Don't build if we already have enough of this robot */
-            if i < 3 && costs.iter().all(|c| robots[i] >= c[i]) {
+    /// Waits until each type of robot can be built and then builds it
+    fn successors(&self, state: &State) -> Vec<(State, u32)> {
+        let mut result = Vec::new();
+        for (i, cost) in self.costs.iter().enumerate() {
+            if i < 3 && state.robots[i] >= self.max_robots[i] {
                 continue;
             }
 
-            for j in 0..time {
-                let start_resources = resources - cost + robots * j;
+            for wait in 0..state.time - 1 {
+                let start_resources = state.resources - cost + state.robots * wait;
                 if start_resources.iter().all(|&a| a >= 0) {
-                    states.push((
-                        robots + Vector4::ith(i, 1),
-                        start_resources + robots,
-                        time - j - 1,
-                    ));
+                    let time = state.time - wait - 1;
+                    let robots = if i < 3 {
+                        state.robots + Vector3::ith(i, 1)
+                    } else {
+                        state.robots
+                    };
+                    let geodes = if i == 3 { time as u32 } else { 0 };
+
+                    let next = State {
+                        robots,
+                        resources: start_resources + state.robots,
+                        time,
+                    };
+                    result.push((next, geodes));
                     break;
                 }
             }
         }
-    }
 
-    best_geodes
+        result
+    }
 }
 
-pub fn star1(input: &str) -> String {
+/// Parses all blueprints
+fn parse_input(input: &str) -> Vec<Blueprint> {
     input
         .split_ascii_whitespace()
         .chunks(32)
+        .into_iter()
+        .map(Blueprint::parse)
+        .collect()
+}
+
+/// Solves each blueprint in its own thread
+fn solve_all(blueprints: &[Blueprint], time: i32) -> Vec<u32> {
+    let beam_width = options::beam_width();
+    thread::scope(|scope| {
+        let handles: Vec<_> = blueprints
+            .iter()
+            .map(|b| scope.spawn(move || b.solve(time, beam_width)))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    })
+}
+
+pub fn star1(input: &str) -> String {
+    solve_all(&parse_input(input), 24)
         .into_iter()
         .enumerate()
-        .map(|(i, b)| (i as i32 + 1) * solve_blueprint(b, 24))
-        .sum::<i32>()
+        .map(|(i, geodes)| (i as u32 + 1) * geodes)
+        .sum::<u32>()
         .to_string()
 }
 
 pub fn star2(input: &str) -> String {
-    input
-        .split_ascii_whitespace()
-        .chunks(32)
+    let blueprints = parse_input(input);
+    solve_all(&blueprints[..blueprints.len().min(3)], 32)
         .into_iter()
-        .take(3)
-        .map(|b| solve_blueprint(b, 32))
-        .product::<i32>()
+        .product::<u32>()
         .to_string()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. \
+Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. \
+Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
+
+    #[test]
+    fn beam_search() {
+        for (blueprint, geodes) in parse_input(EXAMPLE).iter().zip([9, 12]) {
+            assert_eq!(blueprint.solve(24, None), geodes);
+            assert_eq!(blueprint.solve(24, Some(1000)), geodes);
+            assert!(blueprint.solve(24, Some(1)) <= geodes);
+        }
+    }
+}
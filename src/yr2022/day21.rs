@@ -1,4 +1,9 @@
+use num::rational::Ratio;
+use num::{One, Zero};
 use std::collections::HashMap;
+use std::fmt;
+
+use crate::options;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum Operator {
@@ -49,69 +54,207 @@ fn make_tree_from_input(input: &str, use_human: bool) -> Expr {
     )
 }
 
-fn eval(expr: &Expr) -> i64 {
-    match expr {
-        Expr::Human => panic!("tried to eval human"),
-        Expr::Literal(l) => *l,
-        Expr::Op(Operator::Add, l, r) => eval(l) + eval(r),
-        Expr::Op(Operator::Sub, l, r) => eval(l) - eval(r),
-        Expr::Op(Operator::Mul, l, r) => eval(l) * eval(r),
-        Expr::Op(Operator::Div, l, r) => eval(l) / eval(r),
+type Rational = Ratio<i128>;
+
+/// A linear polynomial in the human's number: constant + coefficient * humn
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Linear {
+    constant: Rational,
+    coefficient: Rational,
+}
+
+/// Errors which occur while evaluating or solving the monkeys' equation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum EquationError {
+    /// The human's number is multiplied by itself or appears in a divisor
+    NonLinear,
+    DivisionByZero,
+    /// The equation is false for every number
+    NoSolution,
+    /// The equation is true for every number
+    AnySolution,
+    /// The only solution is not a whole number
+    NotInteger(Rational),
+}
+
+impl fmt::Display for EquationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EquationError::NonLinear => write!(f, "equation is not linear in humn"),
+            EquationError::DivisionByZero => write!(f, "division by zero"),
+            EquationError::NoSolution => write!(f, "equation has no solution"),
+            EquationError::AnySolution => write!(f, "every value of humn is a solution"),
+            EquationError::NotInteger(r) => write!(f, "solution {r} is not an integer"),
+        }
     }
 }
 
-fn contains_human(expr: &Expr) -> bool {
-    match expr {
-        Expr::Human => true,
-        Expr::Literal(_) => false,
-        Expr::Op(_, l, r) => contains_human(l) || contains_human(r),
+impl fmt::Display for Linear {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.coefficient.is_zero() {
+            write!(f, "{}", self.constant)
+        } else if self.constant.is_zero() {
+            write!(f, "{} * humn", self.coefficient)
+        } else {
+            write!(f, "{} * humn + {}", self.coefficient, self.constant)
+        }
     }
 }
 
-fn solve(expr: &Expr, result: i64) -> i64 {
-    match expr {
-        Expr::Human => result,
-        Expr::Literal(_) => panic!("cannot solve literal"),
-        Expr::Op(op, l, r) => {
-            if contains_human(l) {
-                let right = eval(r);
-                solve(
-                    l,
-                    match op {
-                        Operator::Add => result - right,
-                        Operator::Sub => result + right,
-                        Operator::Mul => result / right,
-                        Operator::Div => result * right,
-                    },
-                )
-            } else {
-                let left = eval(l);
-                solve(
-                    r,
-                    match op {
-                        Operator::Add => result - left,
-                        Operator::Sub => left - result,
-                        Operator::Mul => result / left,
-                        Operator::Div => left / result,
-                    },
-                )
+impl Linear {
+    fn constant(value: Rational) -> Linear {
+        Linear {
+            constant: value,
+            coefficient: Rational::zero(),
+        }
+    }
+
+    fn is_constant(&self) -> bool {
+        self.coefficient.is_zero()
+    }
+
+    /// Applies an operator to two polynomials
+    fn apply(self, op: Operator, rhs: Linear) -> Result<Linear, EquationError> {
+        Ok(match op {
+            Operator::Add => Linear {
+                constant: self.constant + rhs.constant,
+                coefficient: self.coefficient + rhs.coefficient,
+            },
+            Operator::Sub => Linear {
+                constant: self.constant - rhs.constant,
+                coefficient: self.coefficient - rhs.coefficient,
+            },
+            Operator::Mul if self.is_constant() => rhs.scale(self.constant),
+            Operator::Mul if rhs.is_constant() => self.scale(rhs.constant),
+            Operator::Div if rhs.is_constant() => {
+                if rhs.constant.is_zero() {
+                    return Err(EquationError::DivisionByZero);
+                }
+                self.scale(rhs.constant.recip())
             }
+            Operator::Mul | Operator::Div => return Err(EquationError::NonLinear),
+        })
+    }
+
+    /// Multiplies the polynomial by a constant
+    fn scale(self, factor: Rational) -> Linear {
+        Linear {
+            constant: self.constant * factor,
+            coefficient: self.coefficient * factor,
+        }
+    }
+
+    /// Solves self = rhs for the human's number
+    fn solve(self, rhs: Linear) -> Result<Rational, EquationError> {
+        let coefficient = self.coefficient - rhs.coefficient;
+        let constant = rhs.constant - self.constant;
+        if !coefficient.is_zero() {
+            Ok(constant / coefficient)
+        } else if constant.is_zero() {
+            Err(EquationError::AnySolution)
+        } else {
+            Err(EquationError::NoSolution)
         }
     }
 }
 
+/// Evaluates an expression as a linear polynomial in the human's number
+fn eval(expr: &Expr) -> Result<Linear, EquationError> {
+    match expr {
+        Expr::Human => Ok(Linear {
+            constant: Rational::zero(),
+            coefficient: Rational::one(),
+        }),
+        Expr::Literal(l) => Ok(Linear::constant(Rational::from_integer((*l).into()))),
+        Expr::Op(op, l, r) => eval(l)?.apply(*op, eval(r)?),
+    }
+}
+
 pub fn star1(input: &str) -> String {
-    eval(&make_tree_from_input(input, false)).to_string()
+    eval(&make_tree_from_input(input, false))
+        .unwrap_or_else(|e| panic!("error evaluating root: {e}"))
+        .constant
+        .to_string()
 }
 
-pub fn star2(input: &str) -> String {
-    if let Expr::Op(_, l, r) = make_tree_from_input(input, true) {
-        if contains_human(&r) {
-            solve(&r, eval(&l)).to_string()
-        } else {
-            solve(&l, eval(&r)).to_string()
-        }
-    } else {
+/// Solves both sides of the root monkey being equal
+///  The human can only shout whole numbers. In verbose mode, the simplified
+///  equation is printed.
+fn solve_root(root: &Expr) -> Result<i128, EquationError> {
+    let Expr::Op(_, l, r) = root else {
         panic!("invalid root");
+    };
+
+    let (left, right) = (eval(l)?, eval(r)?);
+    if options::verbose() {
+        eprintln!("{left} = {right}");
+    }
+    let solution = left.solve(right)?;
+    if solution.is_integer() {
+        Ok(solution.to_integer())
+    } else {
+        Err(EquationError::NotInteger(solution))
+    }
+}
+
+pub fn star2(input: &str) -> String {
+    solve_root(&make_tree_from_input(input, true))
+        .unwrap_or_else(|e| panic!("error solving equation: {e}"))
+        .to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn solve_str(input: &str) -> Result<i128, EquationError> {
+        solve_root(&make_tree_from_input(input, true))
+    }
+
+    #[test]
+    fn human_both_sides() {
+        // humn * 3 = humn + 8
+        let input = "root: a + b\na: humn * c\nb: humn + d\nc: 3\nd: 8\nhumn: 0";
+        assert_eq!(solve_str(input), Ok(4));
+    }
+
+    #[test]
+    fn inexact_division() {
+        // humn / 4 * 2 = 3
+        let input = "root: a + b\na: c * d\nc: humn / e\nd: 2\ne: 4\nb: 3\nhumn: 0";
+        assert_eq!(solve_str(input), Ok(6));
+    }
+
+    #[test]
+    fn display() {
+        let linear = |constant, coefficient| Linear {
+            constant: Rational::from_integer(constant),
+            coefficient: Rational::from_integer(coefficient),
+        };
+        assert_eq!(linear(3, 2).to_string(), "2 * humn + 3");
+        assert_eq!(linear(0, -1).to_string(), "-1 * humn");
+        assert_eq!(linear(3, 0).to_string(), "3");
+        assert_eq!(linear(1, 0).scale(Rational::new(1, 2)).to_string(), "1/2");
+    }
+
+    #[test]
+    fn errors() {
+        let non_linear = "root: a + b\na: humn * humn\nb: 4\nhumn: 0";
+        assert_eq!(solve_str(non_linear), Err(EquationError::NonLinear));
+
+        let divisor = "root: a + b\na: b / humn\nb: 4\nhumn: 0";
+        assert_eq!(solve_str(divisor), Err(EquationError::NonLinear));
+
+        let no_solution = "root: a + b\na: humn + c\nb: humn - c\nc: 1\nhumn: 0";
+        assert_eq!(solve_str(no_solution), Err(EquationError::NoSolution));
+
+        let any_solution = "root: a + b\na: humn - c\nb: humn - c\nc: 1\nhumn: 0";
+        assert_eq!(solve_str(any_solution), Err(EquationError::AnySolution));
+
+        let not_integer = "root: a + b\na: humn * c\nb: 3\nc: 2\nhumn: 0";
+        assert_eq!(
+            solve_str(not_integer),
+            Err(EquationError::NotInteger(Rational::new(3, 2)))
+        );
     }
 }
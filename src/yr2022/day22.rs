@@ -1,5 +1,5 @@
 use crate::direction::Direction;
-use nalgebra::Vector2;
+use nalgebra::{Vector2, Vector3};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 enum Tile {
@@ -111,78 +111,246 @@ pub fn star1(input: &str) -> String {
     solve(input, |grid, dir, pos| (dir, wrap_position(grid, dir, pos)))
 }
 
-fn wrap_cube_small(dir: Direction, pos: Vector2<i32>) -> (Direction, Vector2<i32>) {
-    let s = 4;
-    let block = (pos - dir.to_vec_neg()) / s;
-    let pmod = (pos - dir.to_vec_neg()).map(|v| v % s);
-
-    let (dir, x, y) = match (dir, block.x, block.y) {
-        (Direction::North, 0, 1) => (Direction::South, 3 * s - pmod.x - 1, 0),
-        (Direction::North, 2, 0) => (Direction::South, s - pmod.x - 1, s),
-
-        (Direction::North, 1, 1) => (Direction::East, 2 * s, pmod.x),
-        (Direction::West, 2, 0) => (Direction::South, s + pmod.y, s),
-
-        (Direction::North, 3, 2) => (Direction::West, 3 * s - 1, 2 * s - pmod.x - 1),
-        (Direction::East, 2, 1) => (Direction::South, 4 * s - pmod.y - 1, 2 * s),
-
-        (Direction::South, 0, 1) => (Direction::North, 3 * s - pmod.x - 1, 3 * s - 1),
-        (Direction::South, 2, 2) => (Direction::North, s - pmod.x - 1, 2 * s - 1),
-
-        (Direction::South, 1, 1) => (Direction::East, 2 * s, 2 * s + pmod.x),
-        (Direction::West, 2, 2) => (Direction::North, s + pmod.y, 2 * s - 1),
+/// A face of the cube, found by folding up the net
+#[derive(Clone, Copy, Debug)]
+struct Face {
+    /// Position of the face in the net, in units of the face size
+    block: Vector2<i32>,
+    /// Outward normal of the face
+    normal: Vector3<i32>,
+    /// 3D directions of the map's +x and +y axes on this face
+    right: Vector3<i32>,
+    down: Vector3<i32>,
+}
 
-        (Direction::South, 3, 2) => (Direction::East, 0, 2 * s - pmod.x - 1),
-        (Direction::West, 0, 1) => (Direction::North, 4 * s - pmod.y - 1, 3 * s - 1),
+impl Face {
+    /// Returns the outward direction of one of the face's edges
+    ///  This is also the normal of the face on the other side of that edge.
+    fn edge_normal(&self, dir: Direction) -> Vector3<i32> {
+        match dir {
+            Direction::North => -self.down,
+            Direction::East => self.right,
+            Direction::South => self.down,
+            Direction::West => -self.right,
+        }
+    }
 
-        (Direction::East, 2, 0) => (Direction::West, 4 * s - 1, 3 * s - pmod.y - 1),
-        (Direction::East, 3, 2) => (Direction::West, 3 * s - 1, s - pmod.y - 1),
+    /// Returns the 3D direction in which the map coordinate along an edge increases
+    fn edge_tangent(&self, dir: Direction) -> Vector3<i32> {
+        match dir {
+            Direction::North | Direction::South => self.right,
+            Direction::East | Direction::West => self.down,
+        }
+    }
 
-        _ => panic!("unhandled wrap"),
-    };
+    /// Returns the face obtained by rolling the cube over one of this face's edges
+    fn roll(&self, dir: Direction) -> Face {
+        let (normal, right, down) = match dir {
+            Direction::North => (-self.down, self.right, self.normal),
+            Direction::East => (self.right, -self.normal, self.down),
+            Direction::South => (self.down, self.right, -self.normal),
+            Direction::West => (-self.right, self.normal, self.down),
+        };
 
-    (dir, Vector2::new(x, y))
+        Face {
+            block: self.block + dir.to_vec_neg(),
+            normal,
+            right,
+            down,
+        }
+    }
 }
 
-fn wrap_cube_big(dir: Direction, pos: Vector2<i32>) -> (Direction, Vector2<i32>) {
-    let s = 50;
-    let block = (pos - dir.to_vec_neg()) / s;
-    let pmod = (pos - dir.to_vec_neg()).map(|v| v % s);
+/// A cube net folded into a cube
+#[derive(Clone, Debug)]
+struct Cube {
+    size: i32,
+    faces: Vec<Face>,
+}
 
-    let (dir, x, y) = match (dir, block.x, block.y) {
-        (Direction::North, 0, 2) => (Direction::East, s, s + pmod.x),
-        (Direction::West, 1, 1) => (Direction::South, pmod.y, 2 * s),
+impl Cube {
+    /// Folds the net in the grid into a cube
+    ///  The face size is determined from the number of tiles on the map, and
+    ///  each face's orientation is found by rolling the cube from the first
+    ///  face across the net.
+    fn fold(grid: &[Vec<Tile>]) -> Cube {
+        let tiles = grid
+            .iter()
+            .flatten()
+            .filter(|&&t| t != Tile::OffMap)
+            .count();
+        let size = (tiles / 6).isqrt() as i32;
+        assert_eq!(6 * size * size, tiles as i32, "map is not a cube net");
+
+        let is_face = |block: Vector2<i32>| grid_get(grid, block * size) != Tile::OffMap;
+        let first_x = grid[0].iter().position(|&t| t != Tile::OffMap).unwrap() as i32;
+        let mut faces = vec![Face {
+            block: Vector2::new(first_x / size, 0),
+            normal: Vector3::z(),
+            right: Vector3::x(),
+            down: Vector3::y(),
+        }];
+
+        let mut next = 0;
+        while next < faces.len() {
+            let face = faces[next];
+            for dir in Direction::iter() {
+                let block = face.block + dir.to_vec_neg();
+                if block.x >= 0 && is_face(block) && faces.iter().all(|f| f.block != block) {
+                    faces.push(face.roll(dir));
+                }
+            }
+            next += 1;
+        }
 
-        (Direction::North, 1, 0) => (Direction::East, 0, 3 * s + pmod.x),
-        (Direction::West, 0, 3) => (Direction::South, s + pmod.y, 0),
+        assert_eq!(faces.len(), 6, "map is not a cube net");
+        assert!(
+            faces
+                .iter()
+                .all(|a| faces.iter().filter(|b| b.normal == a.normal).count() == 1),
+            "net does not fold into a cube"
+        );
+        Cube { size, faces }
+    }
 
-        (Direction::North, 2, 0) => (Direction::North, pmod.x, 4 * s - 1),
-        (Direction::South, 0, 3) => (Direction::South, 2 * s + pmod.x, 0),
+    /// Finds the face with the given outward normal
+    fn face_by_normal(&self, normal: Vector3<i32>) -> &Face {
+        self.faces.iter().find(|f| f.normal == normal).unwrap()
+    }
 
-        (Direction::South, 1, 2) => (Direction::West, s - 1, 3 * s + pmod.x),
-        (Direction::East, 0, 3) => (Direction::North, s + pmod.y, 3 * s - 1),
+    /// Returns the new direction and position after walking off the edge of a face
+    fn wrap(&self, dir: Direction, pos: Vector2<i32>) -> (Direction, Vector2<i32>) {
+        let s = self.size;
+        let from = self.faces.iter().find(|f| f.block == pos / s).unwrap();
+        let to = self.face_by_normal(from.edge_normal(dir));
+
+        // The edge of the new face which touches the old face
+        let to_edge = Direction::iter()
+            .find(|&d| to.edge_normal(d) == from.normal)
+            .unwrap();
+
+        let local = pos.map(|v| v % s);
+        let mut offset = match dir {
+            Direction::North | Direction::South => local.x,
+            Direction::East | Direction::West => local.y,
+        };
+        if to.edge_tangent(to_edge) != from.edge_tangent(dir) {
+            offset = s - 1 - offset;
+        }
 
-        (Direction::South, 2, 0) => (Direction::West, 2 * s - 1, s + pmod.x),
-        (Direction::East, 1, 1) => (Direction::North, 2 * s + pmod.y, s - 1),
+        let new_local = match to_edge {
+            Direction::North => Vector2::new(offset, 0),
+            Direction::East => Vector2::new(s - 1, offset),
+            Direction::South => Vector2::new(offset, s - 1),
+            Direction::West => Vector2::new(0, offset),
+        };
 
-        (Direction::East, 2, 0) => (Direction::West, 2 * s - 1, 3 * s - pmod.y - 1),
-        (Direction::East, 1, 2) => (Direction::West, 3 * s - 1, s - pmod.y - 1),
+        (to_edge.reverse(), to.block * s + new_local)
+    }
+}
 
-        (Direction::West, 1, 0) => (Direction::East, 0, 3 * s - pmod.y - 1),
-        (Direction::West, 0, 2) => (Direction::East, s, s - pmod.y - 1),
+pub fn star2(input: &str) -> String {
+    let cube = Cube::fold(&parse_grid(input));
+    solve(input, |_, dir, pos| cube.wrap(dir, pos))
+}
 
-        _ => panic!("unhandled wrap"),
-    };
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dihedral::Dihedral;
+
+    /// All 11 cube nets, with # marking each face
+    const NETS: [&str; 11] = [
+        "#\n####\n#",
+        "#\n####\n.#",
+        "#\n####\n..#",
+        "#\n####\n...#",
+        ".#\n####\n.#",
+        ".#\n####\n..#",
+        "##\n.###\n.#",
+        "##\n.###\n..#",
+        "##\n.###\n...#",
+        "##\n.##\n..##",
+        "###\n..###",
+    ];
+
+    /// Rotates or reflects a net
+    ///  The net is padded to a square before transforming, and any rows and
+    ///  columns without faces are trimmed afterwards like in the puzzle input.
+    fn transform_net(net: &str, transform: Dihedral) -> String {
+        let lines: Vec<&[u8]> = net.lines().map(str::as_bytes).collect();
+        let size = lines
+            .len()
+            .max(lines.iter().map(|l| l.len()).max().unwrap());
+        let inverse = transform.inverse();
+        let rows: Vec<String> = (0..size)
+            .map(|y| {
+                (0..size)
+                    .map(|x| {
+                        let (x, y) = inverse.apply((x, y), size);
+                        lines
+                            .get(y)
+                            .and_then(|l| l.get(x))
+                            .map_or('.', |&c| char::from(c))
+                    })
+                    .collect::<String>()
+            })
+            .filter(|row| row.contains('#'))
+            .collect();
+
+        let indent = rows.iter().map(|row| row.find('#').unwrap()).min().unwrap();
+        rows.iter()
+            .map(|row| row[indent..].trim_end_matches('.'))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 
-    (dir, Vector2::new(x, y))
-}
+    /// Expands a net into an empty map with the given face size
+    fn net_grid(net: &str, size: usize) -> Vec<Vec<Tile>> {
+        net.lines()
+            .flat_map(|line| {
+                let row: Vec<Tile> = line
+                    .chars()
+                    .flat_map(|c| {
+                        let tile = if c == '#' { Tile::Empty } else { Tile::OffMap };
+                        std::iter::repeat_n(tile, size)
+                    })
+                    .collect();
+                std::iter::repeat_n(row, size)
+            })
+            .collect()
+    }
 
-pub fn star2(input: &str) -> String {
-    solve(input, |grid, dir, pos| {
-        if grid.len() > 100 {
-            wrap_cube_big(dir, pos)
-        } else {
-            wrap_cube_small(dir, pos)
+    #[test]
+    fn walk_around_all_nets() {
+        let size = 3;
+        for net in NETS
+            .iter()
+            .flat_map(|&net| Dihedral::all().map(move |d| transform_net(net, d)))
+        {
+            let grid = net_grid(&net, size as usize);
+            let cube = Cube::fold(&grid);
+            assert_eq!(cube.size, size);
+
+            for (y, row) in grid.iter().enumerate() {
+                for x in (0..row.len()).filter(|&x| row[x] != Tile::OffMap) {
+                    let start = Vector2::new(x as i32, y as i32);
+                    for start_dir in Direction::iter() {
+                        // Walking 4 faces in a straight line returns to the start
+                        let (mut pos, mut dir) = (start, start_dir);
+                        for _ in 0..4 * size {
+                            let next = pos + dir.to_vec_neg();
+                            if grid_get(&grid, next) == Tile::OffMap {
+                                (dir, pos) = cube.wrap(dir, pos);
+                                assert_ne!(grid_get(&grid, pos), Tile::OffMap, "net {net:?}");
+                            } else {
+                                pos = next;
+                            }
+                        }
+                        assert_eq!((pos, dir), (start, start_dir), "net {net:?}");
+                    }
+                }
+            }
         }
-    })
+    }
 }
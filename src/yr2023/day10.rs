@@ -33,8 +33,7 @@ fn iterate_loop(grid: &DMatrix<u8>) -> impl Iterator<Item = (Vector2<i32>, Direc
     let first_dir = Direction::iter()
         .find(|&dir| {
             grid_get_pipe(grid, vec_spos + dir.to_vec_neg())
-                .filter(|dirs| dirs.contains(&dir.reverse()))
-                .is_some()
+                .is_some_and(|dirs| dirs.contains(&dir.reverse()))
         })
         .unwrap();
 
@@ -1,7 +1,52 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use nalgebra::DMatrix;
+
+fn parse_patterns(input: &str) -> impl Iterator<Item = DMatrix<bool>> + '_ {
+    input.split("\n\n").map(|block| {
+        let height = block.lines().count();
+        let width = block.lines().next().unwrap().len();
+        DMatrix::from_row_iterator(
+            height,
+            width,
+            block.bytes().filter(|&b| b != b'\n').map(|b| b == b'#'),
+        )
+    })
+}
+
+/// Finds a horizontal reflection line with exactly `smudges` differences
+///  Returns the number of rows above the line.
+fn find_reflection(pattern: &DMatrix<bool>, smudges: usize) -> Option<usize> {
+    (1..pattern.nrows()).find(|&split| {
+        let size = split.min(pattern.nrows() - split);
+        (0..size)
+            .map(|i| {
+                pattern
+                    .row(split - 1 - i)
+                    .iter()
+                    .zip(pattern.row(split + i).iter())
+                    .filter(|(a, b)| a != b)
+                    .count()
+            })
+            .sum::<usize>()
+            == smudges
+    })
+}
+
+fn solve(input: &str, smudges: usize) -> String {
+    parse_patterns(input)
+        .map(|p| {
+            find_reflection(&p, smudges)
+                .map(|rows| rows * 100)
+                .or_else(|| find_reflection(&p.transpose(), smudges))
+                .expect("no reflection found")
+        })
+        .sum::<usize>()
+        .to_string()
+}
+
+pub fn star1(input: &str) -> String {
+    solve(input, 0)
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+pub fn star2(input: &str) -> String {
+    solve(input, 1)
 }
@@ -1,7 +1,73 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use std::collections::HashMap;
+
+use nalgebra::DMatrix;
+
+fn parse_platform(input: &str) -> DMatrix<u8> {
+    let height = input.lines().count();
+    let width = input.lines().next().unwrap().len();
+    DMatrix::from_row_iterator(height, width, input.bytes().filter(u8::is_ascii_graphic))
+}
+
+/// Tilts the platform so all the round rocks roll north
+fn tilt_north(platform: &mut DMatrix<u8>) {
+    for mut column in platform.column_iter_mut() {
+        let mut free = 0;
+        for row in 0..column.len() {
+            match column[row] {
+                b'#' => free = row + 1,
+                b'O' => {
+                    column.swap_rows(row, free);
+                    free += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Rotates the platform clockwise
+fn rotate(platform: &DMatrix<u8>) -> DMatrix<u8> {
+    let (rows, cols) = platform.shape();
+    DMatrix::from_fn(cols, rows, |r, c| platform[(rows - 1 - c, r)])
+}
+
+fn north_load(platform: &DMatrix<u8>) -> usize {
+    platform
+        .row_iter()
+        .enumerate()
+        .map(|(i, row)| (platform.nrows() - i) * row.iter().filter(|&&b| b == b'O').count())
+        .sum()
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+pub fn star1(input: &str) -> String {
+    let mut platform = parse_platform(input);
+    tilt_north(&mut platform);
+    north_load(&platform).to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    const CYCLES: usize = 1_000_000_000;
+
+    let mut platform = parse_platform(input);
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+
+    for cycle in 0..CYCLES {
+        if let Some(&start) = seen.get(&platform) {
+            let period = cycle - start;
+            let final_platform: &DMatrix<u8> = &history[start + (CYCLES - start) % period];
+            return north_load(final_platform).to_string();
+        }
+
+        seen.insert(platform.clone(), cycle);
+        history.push(platform.clone());
+
+        // Tilt north, west, south, east by rotating clockwise each time
+        for _ in 0..4 {
+            tilt_north(&mut platform);
+            platform = rotate(&platform);
+        }
+    }
+
+    north_load(&platform).to_string()
 }
@@ -1,7 +1,38 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+fn hash(s: &str) -> usize {
+    s.bytes()
+        .fold(0, |acc, b| ((acc + usize::from(b)) * 17) % 256)
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+pub fn star1(input: &str) -> String {
+    input.split(',').map(hash).sum::<usize>().to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    let mut boxes: Vec<Vec<(&str, usize)>> = vec![Vec::new(); 256];
+    for step in input.split(',') {
+        if let Some((label, focal)) = step.split_once('=') {
+            let lenses = &mut boxes[hash(label)];
+            let focal = focal.parse().unwrap();
+            if let Some(lens) = lenses.iter_mut().find(|(l, _)| *l == label) {
+                lens.1 = focal;
+            } else {
+                lenses.push((label, focal));
+            }
+        } else {
+            let label = step.trim_end_matches('-');
+            boxes[hash(label)].retain(|(l, _)| *l != label);
+        }
+    }
+
+    boxes
+        .iter()
+        .enumerate()
+        .flat_map(|(b, lenses)| {
+            lenses
+                .iter()
+                .enumerate()
+                .map(move |(slot, (_, focal))| (b + 1) * (slot + 1) * focal)
+        })
+        .sum::<usize>()
+        .to_string()
 }
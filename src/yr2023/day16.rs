@@ -1,7 +1,73 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use nalgebra::{DMatrix, Vector2, try_convert};
+
+use crate::direction::Direction;
+
+fn parse_grid(input: &str) -> DMatrix<u8> {
+    let height = input.lines().count();
+    let width = input.lines().next().unwrap().len();
+    DMatrix::from_row_iterator(height, width, input.bytes().filter(u8::is_ascii_graphic))
+}
+
+/// Returns the directions a beam leaves a tile in
+fn deflect(tile: u8, dir: Direction) -> impl Iterator<Item = Direction> {
+    let horizontal = matches!(dir, Direction::East | Direction::West);
+    let (first, second) = match (tile, horizontal) {
+        (b'/', true) | (b'\\', false) => (dir.anticlockwise(), None),
+        (b'/', false) | (b'\\', true) => (dir.clockwise(), None),
+        (b'|', true) | (b'-', false) => (dir.clockwise(), Some(dir.anticlockwise())),
+        _ => (dir, None),
+    };
+    std::iter::once(first).chain(second)
+}
+
+fn energize(grid: &DMatrix<u8>, start: Vector2<i32>, dir: Direction) -> usize {
+    let mut seen = DMatrix::from_element(grid.nrows(), grid.ncols(), 0u8);
+    let mut open = vec![(start, dir)];
+
+    while let Some((pos, dir)) = open.pop() {
+        let Some(posus) = try_convert::<_, Vector2<usize>>(pos) else {
+            continue;
+        };
+        let Some(seen_mask) = seen.get_mut((posus.y, posus.x)) else {
+            continue;
+        };
+        if *seen_mask & (1 << dir as u8) != 0 {
+            continue;
+        }
+
+        *seen_mask |= 1 << dir as u8;
+        for new_dir in deflect(grid[(posus.y, posus.x)], dir) {
+            open.push((pos + new_dir.to_vec_neg(), new_dir));
+        }
+    }
+
+    seen.iter().filter(|&&m| m != 0).count()
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+pub fn star1(input: &str) -> String {
+    energize(&parse_grid(input), Vector2::zeros(), Direction::East).to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    let grid = parse_grid(input);
+    let (height, width) = (grid.nrows() as i32, grid.ncols() as i32);
+    let starts = (0..width)
+        .flat_map(|x| {
+            [
+                (Vector2::new(x, 0), Direction::South),
+                (Vector2::new(x, height - 1), Direction::North),
+            ]
+        })
+        .chain((0..height).flat_map(|y| {
+            [
+                (Vector2::new(0, y), Direction::East),
+                (Vector2::new(width - 1, y), Direction::West),
+            ]
+        }));
+
+    starts
+        .map(|(pos, dir)| energize(&grid, pos, dir))
+        .max()
+        .unwrap()
+        .to_string()
 }
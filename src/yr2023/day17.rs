@@ -1,7 +1,75 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use nalgebra::{DMatrix, Vector2, try_convert};
+
+use crate::direction::Direction;
+
+fn parse_map(input: &str) -> DMatrix<u32> {
+    let height = input.lines().count();
+    let width = input.lines().next().unwrap().len();
+    DMatrix::from_row_iterator(height, width, input.chars().filter_map(|c| c.to_digit(10)))
+}
+
+/// Finds the minimum heat loss moving between `min_run` and `max_run` blocks
+/// in a straight line before turning
+fn min_heat_loss(map: &DMatrix<u32>, min_run: usize, max_run: usize) -> u32 {
+    // States are a position and whether the last move was horizontal
+    let index = |pos: Vector2<usize>, horizontal: bool| {
+        (pos.y * map.ncols() + pos.x) * 2 + usize::from(horizontal)
+    };
+    let end = Vector2::new(map.ncols() - 1, map.nrows() - 1);
+    let mut costs = vec![u32::MAX; map.len() * 2];
+    let mut open = BinaryHeap::new();
+    for horizontal in [false, true] {
+        costs[index(Vector2::zeros(), horizontal)] = 0;
+        open.push(Reverse((0, 0usize, 0usize, horizontal)));
+    }
+
+    while let Some(Reverse((cost, x, y, horizontal))) = open.pop() {
+        let pos = Vector2::new(x, y);
+        if pos == end {
+            return cost;
+        }
+        if cost > costs[index(pos, horizontal)] {
+            continue;
+        }
+
+        // Turn and move up to max_run blocks
+        let dirs = if horizontal {
+            [Direction::North, Direction::South]
+        } else {
+            [Direction::East, Direction::West]
+        };
+        for dir in dirs {
+            let mut next_cost = cost;
+            for steps in 1..=max_run as i32 {
+                let Some(next) =
+                    try_convert::<_, Vector2<usize>>(pos.cast::<i32>() + dir.to_vec_neg() * steps)
+                else {
+                    break;
+                };
+                let Some(&loss) = map.get((next.y, next.x)) else {
+                    break;
+                };
+
+                next_cost += loss;
+                let next_index = index(next, !horizontal);
+                if steps as usize >= min_run && next_cost < costs[next_index] {
+                    costs[next_index] = next_cost;
+                    open.push(Reverse((next_cost, next.x, next.y, !horizontal)));
+                }
+            }
+        }
+    }
+
+    panic!("no path to the factory");
+}
+
+pub fn star1(input: &str) -> String {
+    min_heat_loss(&parse_map(input), 1, 3).to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+pub fn star2(input: &str) -> String {
+    min_heat_loss(&parse_map(input), 4, 10).to_string()
 }
@@ -1,7 +1,51 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use nalgebra::Vector2;
+
+use crate::direction::Direction;
+
+fn parse_direction(c: u8) -> Direction {
+    match c {
+        b'U' | b'3' => Direction::North,
+        b'R' | b'0' => Direction::East,
+        b'D' | b'1' => Direction::South,
+        b'L' | b'2' => Direction::West,
+        _ => panic!("invalid direction {c}"),
+    }
+}
+
+/// Calculates the lagoon area from a list of dig instructions
+///  Uses the shoelace formula for the interior plus half the trench (Pick's theorem).
+fn lagoon_area(steps: impl Iterator<Item = (Direction, i64)>) -> i64 {
+    let mut pos = Vector2::zeros();
+    let mut twice_area = 0;
+    let mut perimeter = 0;
+
+    for (dir, len) in steps {
+        let next = pos + dir.to_vec() * len;
+        twice_area += pos.x * next.y - next.x * pos.y;
+        perimeter += len;
+        pos = next;
+    }
+
+    twice_area.abs() / 2 + perimeter / 2 + 1
+}
+
+pub fn star1(input: &str) -> String {
+    lagoon_area(input.lines().map(|line| {
+        let mut parts = line.split(' ');
+        let dir = parse_direction(parts.next().unwrap().as_bytes()[0]);
+        (dir, parts.next().unwrap().parse().unwrap())
+    }))
+    .to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+pub fn star2(input: &str) -> String {
+    lagoon_area(input.lines().map(|line| {
+        let colour = line.rsplit_once('#').unwrap().1.trim_end_matches(')');
+        let (len, dir) = colour.split_at(5);
+        (
+            parse_direction(dir.as_bytes()[0]),
+            i64::from_str_radix(len, 16).unwrap(),
+        )
+    }))
+    .to_string()
 }
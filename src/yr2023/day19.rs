@@ -1,7 +1,131 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug)]
+struct Rule<'a> {
+    condition: Option<(usize, bool, u32)>,
+    target: &'a str,
+}
+
+type Workflows<'a> = HashMap<&'a str, Vec<Rule<'a>>>;
+type Part = [u32; 4];
+
+fn category(c: u8) -> usize {
+    match c {
+        b'x' => 0,
+        b'm' => 1,
+        b'a' => 2,
+        b's' => 3,
+        _ => panic!("invalid category {c}"),
+    }
+}
+
+fn parse_input(input: &str) -> (Workflows<'_>, Vec<Part>) {
+    let (workflows, parts) = input.split_once("\n\n").unwrap();
+    (
+        workflows
+            .lines()
+            .map(|line| {
+                let (name, rules) = line.trim_end_matches('}').split_once('{').unwrap();
+                let rules = rules
+                    .split(',')
+                    .map(|rule| {
+                        if let Some((cond, target)) = rule.split_once(':') {
+                            let bytes = cond.as_bytes();
+                            Rule {
+                                condition: Some((
+                                    category(bytes[0]),
+                                    bytes[1] == b'<',
+                                    cond[2..].parse().unwrap(),
+                                )),
+                                target,
+                            }
+                        } else {
+                            Rule {
+                                condition: None,
+                                target: rule,
+                            }
+                        }
+                    })
+                    .collect();
+                (name, rules)
+            })
+            .collect(),
+        parts
+            .lines()
+            .map(|line| {
+                let mut part = [0; 4];
+                for (i, value) in line.trim_matches(['{', '}']).split(',').enumerate() {
+                    part[i] = value[2..].parse().unwrap();
+                }
+                part
+            })
+            .collect(),
+    )
+}
+
+fn is_accepted(workflows: &Workflows, part: &Part) -> bool {
+    let mut current = "in";
+    while current != "A" && current != "R" {
+        current = workflows[current]
+            .iter()
+            .find(|rule| match rule.condition {
+                Some((cat, true, value)) => part[cat] < value,
+                Some((cat, false, value)) => part[cat] > value,
+                None => true,
+            })
+            .unwrap()
+            .target;
+    }
+    current == "A"
+}
+
+pub fn star1(input: &str) -> String {
+    let (workflows, parts) = parse_input(input);
+    parts
+        .iter()
+        .filter(|p| is_accepted(&workflows, p))
+        .map(|p| p.iter().sum::<u32>())
+        .sum::<u32>()
+        .to_string()
+}
+
+/// Counts accepted combinations where each category is a half-open range
+fn count_accepted(workflows: &Workflows, name: &str, mut ranges: [(u32, u32); 4]) -> u64 {
+    match name {
+        "A" => return ranges.iter().map(|(l, h)| u64::from(h - l)).product(),
+        "R" => return 0,
+        _ => {}
+    }
+
+    let mut total = 0;
+    for rule in &workflows[name] {
+        let Some((cat, less, value)) = rule.condition else {
+            return total + count_accepted(workflows, rule.target, ranges);
+        };
+
+        // Split the range into the part matching the rule and the rest
+        let (low, high) = ranges[cat];
+        let (matching, rest) = if less {
+            ((low, high.min(value)), (low.max(value), high))
+        } else {
+            ((low.max(value + 1), high), (low, high.min(value + 1)))
+        };
+
+        if matching.0 < matching.1 {
+            let mut sub = ranges;
+            sub[cat] = matching;
+            total += count_accepted(workflows, rule.target, sub);
+        }
+        if rest.0 >= rest.1 {
+            return total;
+        }
+        ranges[cat] = rest;
+    }
+
+    panic!("workflow {name} has no fallback rule");
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+pub fn star2(input: &str) -> String {
+    let (workflows, _) = parse_input(input);
+    count_accepted(&workflows, "in", [(1, 4001); 4]).to_string()
 }
@@ -1,7 +1,120 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use std::collections::{HashMap, VecDeque};
+
+use num::Integer;
+
+#[derive(Clone, Debug)]
+enum Kind<'a> {
+    Broadcaster,
+    FlipFlop(bool),
+    Conjunction(HashMap<&'a str, bool>),
+}
+
+#[derive(Clone, Debug)]
+struct Module<'a> {
+    kind: Kind<'a>,
+    outputs: Vec<&'a str>,
+}
+
+type Modules<'a> = HashMap<&'a str, Module<'a>>;
+
+fn parse_modules(input: &str) -> Modules<'_> {
+    let mut modules: Modules = input
+        .lines()
+        .map(|line| {
+            let (name, outputs) = line.split_once(" -> ").unwrap();
+            let (kind, name) = match name.as_bytes()[0] {
+                b'%' => (Kind::FlipFlop(false), &name[1..]),
+                b'&' => (Kind::Conjunction(HashMap::new()), &name[1..]),
+                _ => (Kind::Broadcaster, name),
+            };
+            (
+                name,
+                Module {
+                    kind,
+                    outputs: outputs.split(", ").collect(),
+                },
+            )
+        })
+        .collect();
+
+    // Connect up the conjunction inputs
+    let links: Vec<_> = modules
+        .iter()
+        .flat_map(|(&name, m)| m.outputs.iter().map(move |&o| (name, o)))
+        .collect();
+    for (from, to) in links {
+        if let Some(Module {
+            kind: Kind::Conjunction(inputs),
+            ..
+        }) = modules.get_mut(to)
+        {
+            inputs.insert(from, false);
+        }
+    }
+
+    modules
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+/// Presses the button once, calling `observe` with every pulse sent
+fn press_button<'a>(modules: &mut Modules<'a>, mut observe: impl FnMut(&'a str, &'a str, bool)) {
+    let mut queue = VecDeque::from([("button", "broadcaster", false)]);
+    while let Some((from, to, high)) = queue.pop_front() {
+        observe(from, to, high);
+        let Some(module) = modules.get_mut(to) else {
+            continue;
+        };
+
+        let output = match &mut module.kind {
+            Kind::Broadcaster => high,
+            Kind::FlipFlop(_) if high => continue,
+            Kind::FlipFlop(state) => {
+                *state = !*state;
+                *state
+            }
+            Kind::Conjunction(inputs) => {
+                inputs.insert(from, high);
+                !inputs.values().all(|&h| h)
+            }
+        };
+
+        queue.extend(module.outputs.iter().map(|&o| (to, o, output)));
+    }
+}
+
+pub fn star1(input: &str) -> String {
+    let mut modules = parse_modules(input);
+    let mut counts = [0u64; 2];
+    for _ in 0..1000 {
+        press_button(&mut modules, |_, _, high| counts[usize::from(high)] += 1);
+    }
+    (counts[0] * counts[1]).to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    let mut modules = parse_modules(input);
+
+    // rx is fed by a single conjunction, which will send a low pulse once
+    // all of its inputs have sent a high pulse in the same button press.
+    // Assume each input does this periodically and find the LCM.
+    let feeder = *modules
+        .iter()
+        .find(|(_, m)| m.outputs.contains(&"rx"))
+        .expect("nothing feeds rx")
+        .0;
+    let Kind::Conjunction(inputs) = &modules[feeder].kind else {
+        panic!("rx is not fed by a conjunction");
+    };
+
+    let mut periods: HashMap<&str, u64> = inputs.keys().map(|&k| (k, 0)).collect();
+    let mut presses = 0;
+    while periods.values().any(|&p| p == 0) {
+        presses += 1;
+        press_button(&mut modules, |from, to, high| {
+            if high && to == feeder && periods[from] == 0 {
+                periods.insert(from, presses);
+            }
+        });
+    }
+
+    periods.values().fold(1, |acc, p| acc.lcm(p)).to_string()
 }
@@ -1,7 +1,73 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use std::collections::HashSet;
+
+use nalgebra::{DMatrix, Vector2};
+
+use crate::direction::Direction;
+
+fn parse_garden(input: &str) -> (DMatrix<bool>, Vector2<i64>) {
+    let height = input.lines().count();
+    let width = input.lines().next().unwrap().len();
+    let cells: Vec<u8> = input.bytes().filter(u8::is_ascii_graphic).collect();
+    let start = cells.iter().position(|&b| b == b'S').unwrap();
+    (
+        DMatrix::from_row_iterator(height, width, cells.iter().map(|&b| b == b'#')),
+        Vector2::new((start % width) as i64, (start / width) as i64),
+    )
+}
+
+/// Counts the plots reachable in exactly the given number of steps
+///  The garden is repeated infinitely in every direction.
+fn reachable(garden: &DMatrix<bool>, start: Vector2<i64>, steps: usize) -> usize {
+    let size = Vector2::new(garden.ncols() as i64, garden.nrows() as i64);
+    let is_rock = |p: Vector2<i64>| {
+        let wrapped = p.zip_map(&size, i64::rem_euclid);
+        garden[(wrapped.y as usize, wrapped.x as usize)]
+    };
+
+    // Plots alternate between reachable on odd and even steps, so only the
+    // first time a plot is reached needs to be considered
+    let mut seen = HashSet::from([start]);
+    let mut frontier = vec![start];
+    let mut counts = [1, 0];
+    for step in 1..=steps {
+        let mut next_frontier = Vec::new();
+        for pos in frontier {
+            for dir in Direction::iter() {
+                let next = pos + dir.to_vec::<i64>();
+                if !is_rock(next) && seen.insert(next) {
+                    next_frontier.push(next);
+                }
+            }
+        }
+        counts[step % 2] += next_frontier.len();
+        frontier = next_frontier;
+    }
+
+    counts[steps % 2]
+}
+
+pub fn star1(input: &str) -> String {
+    let (garden, start) = parse_garden(input);
+    // The example uses fewer steps
+    let steps = if garden.nrows() < 20 { 6 } else { 64 };
+    reachable(&garden, start, steps).to_string()
+}
+
+/// Extrapolates the number of reachable plots for a large number of steps
+///  The start row and column are clear, so the reachable area grows
+///  quadratically each time the steps increase by the garden size.
+fn extrapolate(garden: &DMatrix<bool>, start: Vector2<i64>, steps: usize) -> usize {
+    let size = garden.nrows();
+    let (cycles, rem) = (steps / size, steps % size);
+    let [a, b, c] = [0, 1, 2].map(|i| reachable(garden, start, rem + i * size));
+
+    // Newton forward differences
+    let d1 = b - a;
+    let d2 = c + a - 2 * b;
+    a + cycles * d1 + cycles * cycles.saturating_sub(1) / 2 * d2
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+pub fn star2(input: &str) -> String {
+    let (garden, start) = parse_garden(input);
+    extrapolate(&garden, start, 26_501_365).to_string()
 }
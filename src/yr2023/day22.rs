@@ -1,7 +1,81 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use nalgebra::{DMatrix, Vector3};
+
+#[derive(Clone, Copy, Debug)]
+struct Brick {
+    low: Vector3<usize>,
+    high: Vector3<usize>,
+}
+
+fn parse_bricks(input: &str) -> Vec<Brick> {
+    input
+        .lines()
+        .map(|line| {
+            let (a, b) = line.split_once('~').unwrap();
+            let parse = |s: &str| Vector3::from_iterator(s.split(',').map(|n| n.parse().unwrap()));
+            let (a, b): (Vector3<usize>, _) = (parse(a), parse(b));
+            Brick {
+                low: a.inf(&b),
+                high: a.sup(&b),
+            }
+        })
+        .sorted_by_key(|b| b.low.z)
+        .collect()
+}
+
+/// Drops all the bricks, returning the set of bricks supporting each brick
+fn settle(bricks: &[Brick]) -> Vec<HashSet<usize>> {
+    let max_x = bricks.iter().map(|b| b.high.x).max().unwrap();
+    let max_y = bricks.iter().map(|b| b.high.y).max().unwrap();
+    let mut heights = DMatrix::from_element(max_x + 1, max_y + 1, (0, None));
+    let mut supports = Vec::with_capacity(bricks.len());
+
+    for (i, brick) in bricks.iter().enumerate() {
+        let cells = || (brick.low.x..=brick.high.x).cartesian_product(brick.low.y..=brick.high.y);
+        let base = cells().map(|c| heights[c].0).max().unwrap();
+        supports.push(
+            cells()
+                .filter_map(|c| match heights[c] {
+                    (h, Some(below)) if h == base => Some(below),
+                    _ => None,
+                })
+                .collect(),
+        );
+
+        let top = base + brick.high.z - brick.low.z + 1;
+        for c in cells() {
+            heights[c] = (top, Some(i));
+        }
+    }
+
+    supports
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+pub fn star1(input: &str) -> String {
+    let supports = settle(&parse_bricks(input));
+    let critical: HashSet<_> = supports.iter().filter(|s| s.len() == 1).flatten().collect();
+    (supports.len() - critical.len()).to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    let supports = settle(&parse_bricks(input));
+
+    // Bricks are in order of height, so a brick falls if all its supports fell
+    (0..supports.len())
+        .map(|removed| {
+            let mut fallen = vec![false; supports.len()];
+            fallen[removed] = true;
+            let mut count = 0;
+            for i in removed + 1..supports.len() {
+                if !supports[i].is_empty() && supports[i].iter().all(|&s| fallen[s]) {
+                    fallen[i] = true;
+                    count += 1;
+                }
+            }
+            count
+        })
+        .sum::<usize>()
+        .to_string()
 }
@@ -1,7 +1,137 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use std::collections::HashMap;
+
+use nalgebra::{DMatrix, Vector2, try_convert};
+
+use crate::direction::Direction;
+
+/// Hiking trails compressed into a graph of junctions
+///  edges[i] contains (junction, steps) for each junction reachable from i.
+struct TrailGraph {
+    edges: Vec<Vec<(usize, u32)>>,
+    start: usize,
+    end: usize,
+}
+
+fn parse_map(input: &str) -> DMatrix<u8> {
+    let height = input.lines().count();
+    let width = input.lines().next().unwrap().len();
+    DMatrix::from_row_iterator(height, width, input.bytes().filter(u8::is_ascii_graphic))
+}
+
+fn get(map: &DMatrix<u8>, pos: Vector2<i32>) -> u8 {
+    try_convert::<_, Vector2<usize>>(pos)
+        .and_then(|p| map.get((p.y, p.x)).copied())
+        .unwrap_or(b'#')
+}
+
+/// Returns the directions which can be taken from a tile
+fn exits(map: &DMatrix<u8>, pos: Vector2<i32>, slippery: bool) -> Vec<Direction> {
+    let forced = match get(map, pos) {
+        b'^' => Some(Direction::North),
+        b'>' => Some(Direction::East),
+        b'v' => Some(Direction::South),
+        b'<' => Some(Direction::West),
+        _ => None,
+    };
+
+    Direction::iter()
+        .filter(|&d| !slippery || forced.is_none_or(|f| f == d))
+        .filter(|&d| get(map, pos + d.to_vec_neg()) != b'#')
+        .collect()
+}
+
+impl TrailGraph {
+    fn new(map: &DMatrix<u8>, slippery: bool) -> TrailGraph {
+        let start = Vector2::new(
+            map.row(0).iter().position(|&b| b == b'.').unwrap() as i32,
+            0,
+        );
+        let last_row = map.nrows() - 1;
+        let end = Vector2::new(
+            map.row(last_row).iter().position(|&b| b == b'.').unwrap() as i32,
+            last_row as i32,
+        );
+
+        // Junctions are the start, end and any tile with 3 or more exits
+        let mut junctions = HashMap::from([(start, 0), (end, 1)]);
+        for y in 0..map.nrows() {
+            for x in 0..map.ncols() {
+                let pos = Vector2::new(x as i32, y as i32);
+                if get(map, pos) != b'#' && exits(map, pos, false).len() > 2 {
+                    let id = junctions.len();
+                    junctions.insert(pos, id);
+                }
+            }
+        }
+
+        // Walk along each path between junctions
+        let mut edges = vec![Vec::new(); junctions.len()];
+        for (&jpos, &jid) in &junctions {
+            for first in exits(map, jpos, slippery) {
+                let mut pos = jpos + first.to_vec_neg();
+                let mut dir = first;
+                let mut steps = 1;
+                loop {
+                    if let Some(&other) = junctions.get(&pos) {
+                        edges[jid].push((other, steps));
+                        break;
+                    }
+
+                    let Some(next_dir) = exits(map, pos, slippery)
+                        .into_iter()
+                        .find(|&d| d != dir.reverse())
+                    else {
+                        break;
+                    };
+                    dir = next_dir;
+                    pos += dir.to_vec_neg();
+                    steps += 1;
+                }
+            }
+        }
+
+        TrailGraph {
+            edges,
+            start: 0,
+            end: 1,
+        }
+    }
+
+    /// Finds the longest path using a depth first search
+    fn longest_path(&self, node: usize, visited: u64) -> Option<u32> {
+        if node == self.end {
+            return Some(0);
+        }
+
+        // The end has one neighbour, so once there we must go to the end
+        if let Some(&(_, steps)) = self.edges[node].iter().find(|&&(n, _)| n == self.end) {
+            return Some(steps);
+        }
+
+        self.edges[node]
+            .iter()
+            .filter(|&&(next, _)| visited & (1 << next) == 0)
+            .filter_map(|&(next, steps)| {
+                self.longest_path(next, visited | (1 << next))
+                    .map(|rest| rest + steps)
+            })
+            .max()
+    }
+}
+
+fn solve(input: &str, slippery: bool) -> String {
+    let graph = TrailGraph::new(&parse_map(input), slippery);
+    assert!(graph.edges.len() <= 64, "too many junctions");
+    graph
+        .longest_path(graph.start, 1 << graph.start)
+        .expect("no path to the end")
+        .to_string()
+}
+
+pub fn star1(input: &str) -> String {
+    solve(input, true)
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+pub fn star2(input: &str) -> String {
+    solve(input, false)
 }
@@ -1,7 +1,140 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use itertools::Itertools;
+use nalgebra::{DMatrix, DVector, Matrix3, Vector3};
+use num::rational::{BigRational, Ratio};
+use num::{BigInt, One, Zero};
+
+#[derive(Clone, Copy, Debug)]
+struct Hailstone {
+    pos: Vector3<i64>,
+    vel: Vector3<i64>,
+}
+
+fn parse_hailstones(input: &str) -> Vec<Hailstone> {
+    input
+        .lines()
+        .map(|line| {
+            let (pos, vel) = line.split_once('@').unwrap();
+            let parse =
+                |s: &str| Vector3::from_iterator(s.split(',').map(|n| n.trim().parse().unwrap()));
+            Hailstone {
+                pos: parse(pos),
+                vel: parse(vel),
+            }
+        })
+        .collect()
+}
+
+/// Returns the point where two hailstone paths cross (ignoring z)
+///  Returns None if they are parallel or cross in the past.
+fn intersect_xy(a: &Hailstone, b: &Hailstone) -> Option<(Ratio<i128>, Ratio<i128>)> {
+    let cross = |u: (i128, i128), v: (i128, i128)| u.0 * v.1 - u.1 * v.0;
+    let va = (i128::from(a.vel.x), i128::from(a.vel.y));
+    let vb = (i128::from(b.vel.x), i128::from(b.vel.y));
+    let dp = (i128::from(b.pos.x - a.pos.x), i128::from(b.pos.y - a.pos.y));
+
+    let det = cross(va, vb);
+    if det == 0 {
+        return None;
+    }
+
+    let t = Ratio::new(cross(dp, vb), det);
+    let s = Ratio::new(cross(dp, va), det);
+    if t < Ratio::zero() || s < Ratio::zero() {
+        return None;
+    }
+
+    Some((
+        Ratio::from(i128::from(a.pos.x)) + t * va.0,
+        Ratio::from(i128::from(a.pos.y)) + t * va.1,
+    ))
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+pub fn star1(input: &str) -> String {
+    let hailstones = parse_hailstones(input);
+    // The example uses a smaller test area
+    let (low, high) = if hailstones.len() < 10 {
+        (7, 27)
+    } else {
+        (200_000_000_000_000, 400_000_000_000_000)
+    };
+    let area = Ratio::from(low)..=Ratio::from(high);
+
+    hailstones
+        .iter()
+        .tuple_combinations()
+        .filter_map(|(a, b)| intersect_xy(a, b))
+        .filter(|(x, y)| area.contains(x) && area.contains(y))
+        .count()
+        .to_string()
+}
+
+/// Returns the matrix M such that M * b = a × b
+fn cross_matrix(a: &Vector3<BigRational>) -> Matrix3<BigRational> {
+    let zero = BigRational::zero();
+    Matrix3::new(
+        zero.clone(),
+        -a.z.clone(),
+        a.y.clone(),
+        a.z.clone(),
+        zero.clone(),
+        -a.x.clone(),
+        -a.y.clone(),
+        a.x.clone(),
+        zero,
+    )
+}
+
+/// Solves a square linear system exactly using Gaussian elimination
+fn solve_exact(mut m: DMatrix<BigRational>, mut b: DVector<BigRational>) -> DVector<BigRational> {
+    let n = m.nrows();
+    for col in 0..n {
+        let pivot = (col..n)
+            .find(|&r| !m[(r, col)].is_zero())
+            .expect("system is singular");
+        m.swap_rows(col, pivot);
+        b.swap_rows(col, pivot);
+
+        let scale = BigRational::one() / m[(col, col)].clone();
+        for r in 0..n {
+            if r != col && !m[(r, col)].is_zero() {
+                let factor = m[(r, col)].clone() * scale.clone();
+                for c in col..n {
+                    let value = m[(col, c)].clone() * factor.clone();
+                    m[(r, c)] -= value;
+                }
+                let value = b[col].clone() * factor;
+                b[r] -= value;
+            }
+        }
+    }
+
+    DVector::from_fn(n, |r, _| b[r].clone() / m[(r, r)].clone())
+}
+
+pub fn star2(input: &str) -> String {
+    let hailstones = parse_hailstones(input);
+    let big = |v: &Vector3<i64>| v.map(|n| BigRational::from(BigInt::from(n)));
+
+    // For a rock at P with velocity V, (P - p_i) × (V - v_i) = 0 for every
+    // hailstone. Subtracting pairs of these gives linear equations:
+    //  P × (v_j - v_i) + (p_j - p_i) × V = p_j × v_j - p_i × v_i
+    let mut m = DMatrix::zeros(6, 6);
+    let mut b = DVector::zeros(6);
+    let (p0, v0) = (big(&hailstones[0].pos), big(&hailstones[0].vel));
+    for (row, h) in hailstones[1..3].iter().enumerate() {
+        let (pj, vj) = (big(&h.pos), big(&h.vel));
+        let dv = &vj - &v0;
+        let dp = &pj - &p0;
+        m.view_mut((row * 3, 0), (3, 3))
+            .copy_from(&-cross_matrix(&dv));
+        m.view_mut((row * 3, 3), (3, 3))
+            .copy_from(&cross_matrix(&dp));
+        b.rows_mut(row * 3, 3)
+            .copy_from(&(pj.cross(&vj) - p0.cross(&v0)));
+    }
+
+    let solution = solve_exact(m, b);
+    let sum = solution[0].clone() + solution[1].clone() + solution[2].clone();
+    assert!(sum.is_integer(), "rock position is not an integer");
+    sum.to_integer().to_string()
 }
@@ -1,3 +1,78 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use std::collections::{HashMap, VecDeque};
+
+fn parse_graph(input: &str) -> Vec<Vec<usize>> {
+    let mut ids = HashMap::new();
+    let mut graph: Vec<Vec<usize>> = Vec::new();
+    let mut id = |name| {
+        let next = ids.len();
+        *ids.entry(name).or_insert(next)
+    };
+
+    for line in input.lines() {
+        let (from, tos) = line.split_once(": ").unwrap();
+        let a = id(from);
+        for to in tos.split(' ') {
+            let b = id(to);
+            graph.resize(graph.len().max(a.max(b) + 1), Vec::new());
+            graph[a].push(b);
+            graph[b].push(a);
+        }
+    }
+
+    graph
+}
+
+/// Finds augmenting paths from source to sink using unit edge capacities
+///  Stops after `max_flow + 1` paths and returns the set of nodes reachable from
+///  the source in the residual graph if the flow is exactly `max_flow`.
+fn min_cut_side(
+    graph: &[Vec<usize>],
+    source: usize,
+    sink: usize,
+    max_flow: usize,
+) -> Option<Vec<bool>> {
+    let mut flow: HashMap<(usize, usize), i32> = HashMap::new();
+    let residual = |flow: &HashMap<_, _>, a, b| 1 - flow.get(&(a, b)).copied().unwrap_or(0);
+
+    for paths in 0..=max_flow {
+        // Breadth first search for a path with spare capacity
+        let mut parent = vec![usize::MAX; graph.len()];
+        parent[source] = source;
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            for &next in &graph[node] {
+                if parent[next] == usize::MAX && residual(&flow, node, next) > 0 {
+                    parent[next] = node;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if parent[sink] == usize::MAX {
+            return (paths == max_flow).then(|| parent.iter().map(|&p| p != usize::MAX).collect());
+        }
+
+        // Push flow along the path
+        let mut node = sink;
+        while node != source {
+            let prev = parent[node];
+            *flow.entry((prev, node)).or_default() += 1;
+            *flow.entry((node, prev)).or_default() -= 1;
+            node = prev;
+        }
+    }
+
+    None
+}
+
+pub fn star1(input: &str) -> String {
+    let graph = parse_graph(input);
+    (1..graph.len())
+        .find_map(|sink| min_cut_side(&graph, 0, sink, 3))
+        .map(|side| {
+            let size = side.iter().filter(|&&s| s).count();
+            size * (graph.len() - size)
+        })
+        .expect("no 3 wire cut found")
+        .to_string()
 }
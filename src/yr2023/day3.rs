@@ -10,11 +10,7 @@ fn parse_input(input: &str) -> DMatrix<u8> {
 fn find_num(grid: &DMatrix<u8>, x: i32, y: i32) -> Option<(usize, usize)> {
     let mut xus = x as usize;
     let yus = y as usize;
-    if grid
-        .get((xus, yus))
-        .filter(|&v| v.is_ascii_digit())
-        .is_some()
-    {
+    if grid.get((xus, yus)).is_some_and(u8::is_ascii_digit) {
         while xus > 0 && grid[(xus - 1, yus)].is_ascii_digit() {
             xus -= 1;
         }
@@ -0,0 +1,34 @@
+use itertools::Itertools;
+
+fn parse_lists(input: &str) -> (Vec<u32>, Vec<u32>) {
+    input
+        .lines()
+        .map(|line| {
+            let (l, r) = line.split_once(' ').unwrap();
+            (
+                l.trim().parse::<u32>().unwrap(),
+                r.trim().parse::<u32>().unwrap(),
+            )
+        })
+        .unzip()
+}
+
+pub fn star1(input: &str) -> String {
+    let (mut left, mut right) = parse_lists(input);
+    left.sort_unstable();
+    right.sort_unstable();
+    left.into_iter()
+        .zip(right)
+        .map(|(l, r)| l.abs_diff(r))
+        .sum::<u32>()
+        .to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    let (left, right) = parse_lists(input);
+    let counts = right.into_iter().counts();
+    left.into_iter()
+        .map(|l| l as usize * counts.get(&l).copied().unwrap_or(0))
+        .sum::<usize>()
+        .to_string()
+}
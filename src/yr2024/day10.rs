@@ -0,0 +1,65 @@
+use std::collections::HashSet;
+
+use crate::direction::Direction;
+use itertools::Itertools;
+use nalgebra::{DMatrix, Vector2};
+
+fn parse_map(input: &str) -> DMatrix<u8> {
+    let height = input.lines().count();
+    let width = input.lines().next().unwrap().len();
+    DMatrix::from_row_iterator(
+        height,
+        width,
+        input.bytes().filter(u8::is_ascii_digit).map(|b| b - b'0'),
+    )
+}
+
+/// Calls `found` with the position of every trail end reachable from pos
+fn trails(map: &DMatrix<u8>, pos: Vector2<usize>, found: &mut impl FnMut(Vector2<usize>)) {
+    let height = map[(pos.y, pos.x)];
+    if height == 9 {
+        found(pos);
+        return;
+    }
+
+    for dir in Direction::iter() {
+        if let Some(next) = (pos.cast::<i32>() + dir.to_vec_neg()).try_cast::<usize>()
+            && map.get((next.y, next.x)) == Some(&(height + 1))
+        {
+            trails(map, next, found);
+        }
+    }
+}
+
+fn trailheads(map: &DMatrix<u8>) -> impl Iterator<Item = Vector2<usize>> + '_ {
+    (0..map.nrows())
+        .cartesian_product(0..map.ncols())
+        .filter(|&p| map[p] == 0)
+        .map(|(y, x)| Vector2::new(x, y))
+}
+
+pub fn star1(input: &str) -> String {
+    let map = parse_map(input);
+    trailheads(&map)
+        .map(|start| {
+            let mut ends = HashSet::new();
+            trails(&map, start, &mut |end| {
+                ends.insert(end);
+            });
+            ends.len()
+        })
+        .sum::<usize>()
+        .to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    let map = parse_map(input);
+    trailheads(&map)
+        .map(|start| {
+            let mut count = 0;
+            trails(&map, start, &mut |_| count += 1);
+            count
+        })
+        .sum::<usize>()
+        .to_string()
+}
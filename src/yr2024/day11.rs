@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+
+fn blink(stones: &HashMap<u64, usize>) -> HashMap<u64, usize> {
+    let mut result = HashMap::with_capacity(stones.len());
+    for (&stone, &count) in stones {
+        let digits = stone.checked_ilog10().unwrap_or(0) + 1;
+        if stone == 0 {
+            *result.entry(1).or_default() += count;
+        } else if digits % 2 == 0 {
+            let shift = 10u64.pow(digits / 2);
+            *result.entry(stone / shift).or_default() += count;
+            *result.entry(stone % shift).or_default() += count;
+        } else {
+            *result.entry(stone * 2024).or_default() += count;
+        }
+    }
+    result
+}
+
+fn solve(input: &str, blinks: usize) -> String {
+    let mut stones = HashMap::new();
+    for n in input.split_whitespace() {
+        *stones.entry(n.parse().unwrap()).or_default() += 1;
+    }
+
+    for _ in 0..blinks {
+        stones = blink(&stones);
+    }
+
+    stones.values().sum::<usize>().to_string()
+}
+
+pub fn star1(input: &str) -> String {
+    solve(input, 25)
+}
+
+pub fn star2(input: &str) -> String {
+    solve(input, 75)
+}
@@ -0,0 +1,76 @@
+use crate::direction::Direction;
+use itertools::Itertools;
+use nalgebra::{DMatrix, Vector2};
+
+fn parse_map(input: &str) -> DMatrix<u8> {
+    let height = input.lines().count();
+    let width = input.lines().next().unwrap().len();
+    DMatrix::from_row_iterator(height, width, input.bytes().filter(u8::is_ascii_alphabetic))
+}
+
+fn get(map: &DMatrix<u8>, pos: Vector2<i32>) -> Option<u8> {
+    let y = usize::try_from(pos.y).ok()?;
+    let x = usize::try_from(pos.x).ok()?;
+    map.get((y, x)).copied()
+}
+
+/// Returns (area, perimeter, sides) of every region in the map
+fn regions(map: &DMatrix<u8>) -> Vec<(usize, usize, usize)> {
+    let mut seen = DMatrix::from_element(map.nrows(), map.ncols(), false);
+    let mut result = Vec::new();
+
+    for (y, x) in (0..map.nrows()).cartesian_product(0..map.ncols()) {
+        if seen[(y, x)] {
+            continue;
+        }
+
+        let plant = map[(y, x)];
+        let same = |p: Vector2<i32>| get(map, p) == Some(plant);
+        let mut open = vec![Vector2::new(x as i32, y as i32)];
+        let (mut area, mut perimeter, mut sides) = (0, 0, 0);
+        seen[(y, x)] = true;
+
+        while let Some(pos) = open.pop() {
+            area += 1;
+            for dir in Direction::iter() {
+                let next = pos + dir.to_vec_neg();
+                if same(next) {
+                    let cell = (next.y as usize, next.x as usize);
+                    if !seen[cell] {
+                        seen[cell] = true;
+                        open.push(next);
+                    }
+                } else {
+                    perimeter += 1;
+                }
+
+                // Count corners (which equals the number of sides)
+                let side = pos + dir.clockwise().to_vec_neg();
+                let diagonal = next + dir.clockwise().to_vec_neg();
+                if (!same(next) && !same(side)) || (same(next) && same(side) && !same(diagonal)) {
+                    sides += 1;
+                }
+            }
+        }
+
+        result.push((area, perimeter, sides));
+    }
+
+    result
+}
+
+pub fn star1(input: &str) -> String {
+    regions(&parse_map(input))
+        .into_iter()
+        .map(|(area, perimeter, _)| area * perimeter)
+        .sum::<usize>()
+        .to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    regions(&parse_map(input))
+        .into_iter()
+        .map(|(area, _, sides)| area * sides)
+        .sum::<usize>()
+        .to_string()
+}
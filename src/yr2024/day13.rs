@@ -0,0 +1,61 @@
+use nalgebra::Vector2;
+use regex::Regex;
+
+#[derive(Clone, Copy, Debug)]
+struct Machine {
+    a: Vector2<i64>,
+    b: Vector2<i64>,
+    prize: Vector2<i64>,
+}
+
+fn parse_machines(input: &str) -> Vec<Machine> {
+    let re = Regex::new(r"X[+=](\d+), Y[+=](\d+)").unwrap();
+    input
+        .split("\n\n")
+        .map(|block| {
+            let vecs: Vec<_> = re
+                .captures_iter(block)
+                .map(|c| Vector2::new(c[1].parse().unwrap(), c[2].parse().unwrap()))
+                .collect();
+            Machine {
+                a: vecs[0],
+                b: vecs[1],
+                prize: vecs[2],
+            }
+        })
+        .collect()
+}
+
+/// Solves for the number of A and B presses using Cramer's rule
+fn tokens(m: &Machine) -> Option<i64> {
+    let det = m.a.x * m.b.y - m.a.y * m.b.x;
+    assert_ne!(det, 0, "buttons are linearly dependent");
+
+    let a_num = m.prize.x * m.b.y - m.prize.y * m.b.x;
+    let b_num = m.a.x * m.prize.y - m.a.y * m.prize.x;
+    if a_num % det == 0 && b_num % det == 0 {
+        let (a, b) = (a_num / det, b_num / det);
+        (a >= 0 && b >= 0).then_some(a * 3 + b)
+    } else {
+        None
+    }
+}
+
+fn solve(input: &str, offset: i64) -> String {
+    parse_machines(input)
+        .into_iter()
+        .filter_map(|mut m| {
+            m.prize.add_scalar_mut(offset);
+            tokens(&m)
+        })
+        .sum::<i64>()
+        .to_string()
+}
+
+pub fn star1(input: &str) -> String {
+    solve(input, 0)
+}
+
+pub fn star2(input: &str) -> String {
+    solve(input, 10_000_000_000_000)
+}
@@ -0,0 +1,67 @@
+use nalgebra::Vector2;
+use regex::Regex;
+
+type Robot = (Vector2<i64>, Vector2<i64>);
+
+fn parse_robots(input: &str) -> (Vec<Robot>, Vector2<i64>) {
+    let re = Regex::new(r"p=(-?\d+),(-?\d+) v=(-?\d+),(-?\d+)").unwrap();
+    let robots: Vec<_> = re
+        .captures_iter(input)
+        .map(|c| {
+            let n = |i: usize| c[i].parse::<i64>().unwrap();
+            (Vector2::new(n(1), n(2)), Vector2::new(n(3), n(4)))
+        })
+        .collect();
+
+    // The example uses a smaller room
+    let size = if robots.len() < 20 {
+        Vector2::new(11, 7)
+    } else {
+        Vector2::new(101, 103)
+    };
+    (robots, size)
+}
+
+fn position_at(robot: &Robot, size: Vector2<i64>, time: i64) -> Vector2<i64> {
+    (robot.0 + robot.1 * time).zip_map(&size, i64::rem_euclid)
+}
+
+pub fn star1(input: &str) -> String {
+    let (robots, size) = parse_robots(input);
+    let mid = size / 2;
+    let mut quadrants = [0; 4];
+    for robot in &robots {
+        let pos = position_at(robot, size, 100);
+        if pos.x != mid.x && pos.y != mid.y {
+            quadrants[usize::from(pos.x > mid.x) + 2 * usize::from(pos.y > mid.y)] += 1;
+        }
+    }
+
+    quadrants.iter().product::<usize>().to_string()
+}
+
+/// Finds the time within one period where the given axis is least spread out
+fn min_variance_time(robots: &[Robot], axis: usize, period: i64) -> i64 {
+    (0..period)
+        .min_by_key(|&t| {
+            let values: Vec<_> = robots
+                .iter()
+                .map(|r| (r.0[axis] + r.1[axis] * t).rem_euclid(period))
+                .collect();
+            let mean = values.iter().sum::<i64>() / values.len() as i64;
+            values.iter().map(|v| (v - mean).pow(2)).sum::<i64>()
+        })
+        .unwrap()
+}
+
+pub fn star2(input: &str) -> String {
+    let (robots, size) = parse_robots(input);
+
+    // The x and y coordinates repeat independently, so find the time where
+    // each axis clusters together and combine them with the CRT
+    let tx = min_variance_time(&robots, 0, size.x);
+    let ty = min_variance_time(&robots, 1, size.y);
+    let inverse = (0..size.y).find(|&k| (size.x * k) % size.y == 1).unwrap();
+    let time = tx + size.x * ((ty - tx) * inverse).rem_euclid(size.y);
+    time.to_string()
+}
@@ -0,0 +1,109 @@
+use crate::direction::Direction;
+use nalgebra::{DMatrix, Vector2};
+
+fn parse_input(input: &str, wide: bool) -> (DMatrix<u8>, Vector2<i32>, Vec<Direction>) {
+    let (map_str, moves_str) = input.split_once("\n\n").unwrap();
+    let rows: Vec<Vec<u8>> = map_str
+        .lines()
+        .map(|line| {
+            line.bytes()
+                .flat_map(|b| match (wide, b) {
+                    (false, _) => vec![b],
+                    (true, b'O') => vec![b'[', b']'],
+                    (true, b'@') => vec![b'@', b'.'],
+                    (true, _) => vec![b, b],
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut map = DMatrix::from_fn(rows.len(), rows[0].len(), |y, x| rows[y][x]);
+    let robot = map.iter().position(|&b| b == b'@').unwrap();
+    let robot = Vector2::new((robot / map.nrows()) as i32, (robot % map.nrows()) as i32);
+    map[(robot.y as usize, robot.x as usize)] = b'.';
+
+    let moves = moves_str
+        .bytes()
+        .filter_map(|b| match b {
+            b'^' => Some(Direction::North),
+            b'>' => Some(Direction::East),
+            b'v' => Some(Direction::South),
+            b'<' => Some(Direction::West),
+            _ => None,
+        })
+        .collect();
+
+    (map, robot, moves)
+}
+
+fn cell(pos: Vector2<i32>) -> (usize, usize) {
+    (pos.y as usize, pos.x as usize)
+}
+
+/// Returns true if the object at pos can be pushed in the given direction
+fn can_push(map: &DMatrix<u8>, pos: Vector2<i32>, dir: Direction) -> bool {
+    let next = pos + dir.to_vec_neg();
+    let vertical = matches!(dir, Direction::North | Direction::South);
+    match map[cell(pos)] {
+        b'.' => true,
+        b'[' if vertical => can_push(map, next, dir) && can_push(map, next + Vector2::x(), dir),
+        b']' if vertical => can_push(map, next, dir) && can_push(map, next - Vector2::x(), dir),
+        b'O' | b'[' | b']' => can_push(map, next, dir),
+        _ => false,
+    }
+}
+
+/// Pushes the object at pos (must have checked with `can_push` first)
+fn push(map: &mut DMatrix<u8>, pos: Vector2<i32>, dir: Direction) {
+    let value = map[cell(pos)];
+    if value == b'.' {
+        return;
+    }
+
+    let next = pos + dir.to_vec_neg();
+    let vertical = matches!(dir, Direction::North | Direction::South);
+    let other_half = match value {
+        b'[' if vertical => Some(pos + Vector2::x()),
+        b']' if vertical => Some(pos - Vector2::x()),
+        _ => None,
+    };
+
+    push(map, next, dir);
+    map[cell(next)] = value;
+    map[cell(pos)] = b'.';
+
+    if let Some(other) = other_half {
+        push(map, other, dir);
+    }
+}
+
+fn solve(input: &str, wide: bool) -> String {
+    let (mut map, mut robot, moves) = parse_input(input, wide);
+    for dir in moves {
+        let next = robot + dir.to_vec_neg();
+        if can_push(&map, next, dir) {
+            push(&mut map, next, dir);
+            robot = next;
+        }
+    }
+
+    map.row_iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|&(_, &b)| b == b'O' || b == b'[')
+                .map(|(x, _)| 100 * y + x)
+                .collect::<Vec<_>>()
+        })
+        .sum::<usize>()
+        .to_string()
+}
+
+pub fn star1(input: &str) -> String {
+    solve(input, false)
+}
+
+pub fn star2(input: &str) -> String {
+    solve(input, true)
+}
@@ -0,0 +1,104 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::direction::Direction;
+use nalgebra::{DMatrix, Vector2};
+
+type State = (Vector2<i32>, Direction);
+
+fn parse_maze(input: &str) -> (DMatrix<u8>, Vector2<i32>, Vector2<i32>) {
+    let height = input.lines().count();
+    let width = input.lines().next().unwrap().len();
+    let maze =
+        DMatrix::from_row_iterator(height, width, input.bytes().filter(u8::is_ascii_graphic));
+    let find = |code| {
+        let i = maze.iter().position(|&b| b == code).unwrap();
+        Vector2::new((i / height) as i32, (i % height) as i32)
+    };
+    let (start, end) = (find(b'S'), find(b'E'));
+    (maze, start, end)
+}
+
+fn state_index(maze: &DMatrix<u8>, (pos, dir): State) -> usize {
+    (pos.y as usize * maze.ncols() + pos.x as usize) * 4 + dir as usize
+}
+
+fn index_state(maze: &DMatrix<u8>, index: usize) -> State {
+    let tile = index / 4;
+    let pos = Vector2::new((tile % maze.ncols()) as i32, (tile / maze.ncols()) as i32);
+    (pos, Direction::iter().nth(index % 4).unwrap())
+}
+
+/// Finds the cost to reach every state from the given starting states
+///  If reverse is true, the edges are followed backwards.
+fn dijkstra(maze: &DMatrix<u8>, starts: &[State], reverse: bool) -> Vec<u32> {
+    let mut costs = vec![u32::MAX; maze.len() * 4];
+    let mut open = BinaryHeap::new();
+    for &s in starts {
+        costs[state_index(maze, s)] = 0;
+        open.push(Reverse((0, state_index(maze, s))));
+    }
+
+    while let Some(Reverse((cost, index))) = open.pop() {
+        if cost > costs[index] {
+            continue;
+        }
+
+        let (pos, dir) = index_state(maze, index);
+        let step = if reverse {
+            -dir.to_vec_neg()
+        } else {
+            dir.to_vec_neg()
+        };
+        let forward = pos + step;
+        for (next, next_cost) in [
+            ((forward, dir), cost + 1),
+            ((pos, dir.clockwise()), cost + 1000),
+            ((pos, dir.anticlockwise()), cost + 1000),
+        ] {
+            let next_index = state_index(maze, next);
+            if maze[(next.0.y as usize, next.0.x as usize)] != b'#' && next_cost < costs[next_index]
+            {
+                costs[next_index] = next_cost;
+                open.push(Reverse((next_cost, next_index)));
+            }
+        }
+    }
+
+    costs
+}
+
+fn best_cost(maze: &DMatrix<u8>, costs: &[u32], end: Vector2<i32>) -> u32 {
+    Direction::iter()
+        .map(|d| costs[state_index(maze, (end, d))])
+        .min()
+        .unwrap()
+}
+
+pub fn star1(input: &str) -> String {
+    let (maze, start, end) = parse_maze(input);
+    let costs = dijkstra(&maze, &[(start, Direction::East)], false);
+    best_cost(&maze, &costs, end).to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    let (maze, start, end) = parse_maze(input);
+    let from_start = dijkstra(&maze, &[(start, Direction::East)], false);
+    let best = best_cost(&maze, &from_start, end);
+    let end_states: Vec<_> = Direction::iter()
+        .map(|d| (end, d))
+        .filter(|&s| from_start[state_index(&maze, s)] == best)
+        .collect();
+    let from_end = dijkstra(&maze, &end_states, true);
+
+    // A tile is on a best path if any of its states sum to the best cost
+    (0..maze.len())
+        .filter(|&tile| {
+            (0..4).any(|d| {
+                let (a, b) = (from_start[tile * 4 + d], from_end[tile * 4 + d]);
+                a != u32::MAX && b != u32::MAX && a + b == best
+            })
+        })
+        .count()
+        .to_string()
+}
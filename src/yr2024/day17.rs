@@ -0,0 +1,89 @@
+use itertools::Itertools;
+
+#[derive(Clone, Debug)]
+struct Computer {
+    regs: [u64; 3],
+    program: Vec<u8>,
+}
+
+fn parse_computer(input: &str) -> Computer {
+    let mut numbers = input
+        .lines()
+        .filter_map(|line| line.split_once(": "))
+        .map(|(_, value)| value);
+    Computer {
+        regs: [(); 3].map(|()| numbers.next().unwrap().parse().unwrap()),
+        program: numbers
+            .next()
+            .unwrap()
+            .split(',')
+            .map(|n| n.parse().unwrap())
+            .collect(),
+    }
+}
+
+impl Computer {
+    /// Runs the program, returning its output
+    fn run(&self, a: u64) -> Vec<u8> {
+        let mut regs = self.regs;
+        regs[0] = a;
+        let mut output = Vec::new();
+        let mut ip = 0;
+
+        while let Some(&[opcode, operand]) = self.program.get(ip..ip + 2) {
+            let literal = u64::from(operand);
+            let combo = match operand {
+                0..=3 => literal,
+                4..=6 => regs[usize::from(operand - 4)],
+                _ => panic!("invalid combo operand {operand}"),
+            };
+
+            ip += 2;
+            match opcode {
+                0 => regs[0] >>= combo,
+                1 => regs[1] ^= literal,
+                2 => regs[1] = combo % 8,
+                3 if regs[0] != 0 => ip = operand.into(),
+                3 => {}
+                4 => regs[1] ^= regs[2],
+                5 => output.push((combo % 8) as u8),
+                6 => regs[1] = regs[0] >> combo,
+                7 => regs[2] = regs[0] >> combo,
+                _ => panic!("invalid opcode {opcode}"),
+            }
+        }
+
+        output
+    }
+}
+
+pub fn star1(input: &str) -> String {
+    let computer = parse_computer(input);
+    computer.run(computer.regs[0]).iter().join(",")
+}
+
+/// Builds up A three bits at a time, matching the program from the end
+///  This relies on the program shifting A right by 3 each loop.
+fn find_quine(computer: &Computer, prefix: u64, matched: usize) -> Option<u64> {
+    if matched == computer.program.len() {
+        return Some(prefix);
+    }
+
+    (0..8).find_map(|bits| {
+        let a = (prefix << 3) | bits;
+        let output = computer.run(a);
+        let suffix = &computer.program[computer.program.len() - matched - 1..];
+        if a != 0 && output == suffix {
+            find_quine(computer, a, matched + 1)
+        } else {
+            None
+        }
+    })
+}
+
+pub fn star2(input: &str) -> String {
+    let computer = parse_computer(input);
+    find_quine(&computer, 0, 0)
+        .expect("no solution found")
+        .to_string()
+}
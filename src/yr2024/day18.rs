@@ -0,0 +1,65 @@
+use std::collections::VecDeque;
+
+use crate::direction::Direction;
+use nalgebra::{DMatrix, Vector2};
+
+fn parse_bytes(input: &str) -> (Vec<Vector2<i32>>, usize, usize) {
+    let bytes: Vec<_> = input
+        .lines()
+        .map(|line| {
+            let (x, y) = line.split_once(',').unwrap();
+            Vector2::new(x.parse().unwrap(), y.parse().unwrap())
+        })
+        .collect();
+
+    // The example uses a smaller grid
+    if bytes.len() < 100 {
+        (bytes, 7, 12)
+    } else {
+        (bytes, 71, 1024)
+    }
+}
+
+/// Returns the shortest path length using the first `count` bytes
+fn shortest_path(bytes: &[Vector2<i32>], size: usize, count: usize) -> Option<u32> {
+    let mut grid = DMatrix::from_element(size, size, u32::MAX);
+    for b in &bytes[..count] {
+        grid[(b.y as usize, b.x as usize)] = 0;
+    }
+
+    let end = Vector2::repeat(size as i32 - 1);
+    let mut open = VecDeque::from([(Vector2::zeros(), 0)]);
+    grid[(0, 0)] = 0;
+
+    while let Some((pos, dist)) = open.pop_front() {
+        if pos == end {
+            return Some(dist);
+        }
+
+        for dir in Direction::iter() {
+            let next: Vector2<i32> = pos + dir.to_vec();
+            if let Some(next_us) = next.try_cast::<usize>()
+                && let Some(cell) = grid.get_mut((next_us.y, next_us.x))
+                && *cell == u32::MAX
+            {
+                *cell = dist + 1;
+                open.push_back((next, dist + 1));
+            }
+        }
+    }
+
+    None
+}
+
+pub fn star1(input: &str) -> String {
+    let (bytes, size, count) = parse_bytes(input);
+    shortest_path(&bytes, size, count).unwrap().to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    let (bytes, size, _) = parse_bytes(input);
+    let counts: Vec<_> = (0..=bytes.len()).collect();
+    let blocked = counts.partition_point(|&c| shortest_path(&bytes, size, c).is_some());
+    let byte = bytes[blocked - 1];
+    format!("{},{}", byte.x, byte.y)
+}
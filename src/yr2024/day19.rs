@@ -0,0 +1,36 @@
+fn parse_input(input: &str) -> (Vec<&str>, impl Iterator<Item = &str>) {
+    let (towels, designs) = input.split_once("\n\n").unwrap();
+    (towels.split(", ").collect(), designs.lines())
+}
+
+/// Counts the number of ways the design can be made using towels
+fn arrangements(towels: &[&str], design: &str) -> u64 {
+    let mut ways = vec![0; design.len() + 1];
+    ways[0] = 1;
+    for i in 0..design.len() {
+        if ways[i] != 0 {
+            for towel in towels {
+                if design[i..].starts_with(towel) {
+                    ways[i + towel.len()] += ways[i];
+                }
+            }
+        }
+    }
+    ways[design.len()]
+}
+
+pub fn star1(input: &str) -> String {
+    let (towels, designs) = parse_input(input);
+    designs
+        .filter(|d| arrangements(&towels, d) > 0)
+        .count()
+        .to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    let (towels, designs) = parse_input(input);
+    designs
+        .map(|d| arrangements(&towels, d))
+        .sum::<u64>()
+        .to_string()
+}
@@ -0,0 +1,36 @@
+fn parse_reports(input: &str) -> impl Iterator<Item = Vec<i32>> + '_ {
+    input.lines().map(|line| {
+        line.split_whitespace()
+            .map(|n| n.parse().unwrap())
+            .collect()
+    })
+}
+
+fn is_safe(levels: impl Iterator<Item = i32> + Clone) -> bool {
+    let mut diffs = levels.clone().zip(levels.skip(1)).map(|(a, b)| b - a);
+    let first = diffs.clone().next().unwrap_or(1);
+    diffs.all(|d| (1..=3).contains(&(d * first.signum())))
+}
+
+pub fn star1(input: &str) -> String {
+    parse_reports(input)
+        .filter(|r| is_safe(r.iter().copied()))
+        .count()
+        .to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    parse_reports(input)
+        .filter(|r| {
+            (0..r.len()).any(|skip| {
+                is_safe(
+                    r.iter()
+                        .enumerate()
+                        .filter(move |&(i, _)| i != skip)
+                        .map(|(_, &v)| v),
+                )
+            })
+        })
+        .count()
+        .to_string()
+}
@@ -0,0 +1,55 @@
+use crate::direction::Direction;
+use crate::vector::VectorExt;
+use nalgebra::{DMatrix, Vector2};
+
+/// Returns the position of every track tile in order from start to end
+fn parse_track(input: &str) -> (Vec<Vector2<i32>>, usize) {
+    let height = input.lines().count();
+    let width = input.lines().next().unwrap().len();
+    let grid =
+        DMatrix::from_row_iterator(height, width, input.bytes().filter(u8::is_ascii_graphic));
+    let start = grid.iter().position(|&b| b == b'S').unwrap();
+    let get = |p: Vector2<i32>| grid[(p.y as usize, p.x as usize)];
+
+    let mut track = vec![Vector2::new(
+        (start / height) as i32,
+        (start % height) as i32,
+    )];
+    while get(*track.last().unwrap()) != b'E' {
+        let pos = *track.last().unwrap();
+        let prev = track.len().checked_sub(2).map(|i| track[i]);
+        let next = Direction::iter()
+            .map(|d| pos + d.to_vec_neg())
+            .find(|&n| get(n) != b'#' && Some(n) != prev)
+            .unwrap();
+        track.push(next);
+    }
+
+    (track, width)
+}
+
+/// Counts cheats lasting at most `max_cheat` which save at least threshold
+fn count_cheats(track: &[Vector2<i32>], max_cheat: i32, threshold: usize) -> usize {
+    (0..track.len())
+        .flat_map(|i| (i + threshold..track.len()).map(move |j| (i, j)))
+        .filter(|&(i, j)| {
+            let dist = (track[j] - track[i]).taxicab_norm();
+            dist <= max_cheat && j - i >= dist as usize + threshold
+        })
+        .count()
+}
+
+fn solve(input: &str, max_cheat: i32) -> String {
+    let (track, width) = parse_track(input);
+    // The example uses a smaller threshold
+    let threshold = if width < 20 { 50 } else { 100 };
+    count_cheats(&track, max_cheat, threshold).to_string()
+}
+
+pub fn star1(input: &str) -> String {
+    solve(input, 2)
+}
+
+pub fn star2(input: &str) -> String {
+    solve(input, 20)
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use nalgebra::Vector2;
+
+type Cache = HashMap<(u8, u8, usize), u64>;
+
+fn numeric_pos(key: u8) -> Vector2<i32> {
+    match key {
+        b'7' => Vector2::new(0, 0),
+        b'8' => Vector2::new(1, 0),
+        b'9' => Vector2::new(2, 0),
+        b'4' => Vector2::new(0, 1),
+        b'5' => Vector2::new(1, 1),
+        b'6' => Vector2::new(2, 1),
+        b'1' => Vector2::new(0, 2),
+        b'2' => Vector2::new(1, 2),
+        b'3' => Vector2::new(2, 2),
+        b'0' => Vector2::new(1, 3),
+        b'A' => Vector2::new(2, 3),
+        _ => panic!("invalid numeric key {key}"),
+    }
+}
+
+fn directional_pos(key: u8) -> Vector2<i32> {
+    match key {
+        b'^' => Vector2::new(1, 0),
+        b'A' => Vector2::new(2, 0),
+        b'<' => Vector2::new(0, 1),
+        b'v' => Vector2::new(1, 1),
+        b'>' => Vector2::new(2, 1),
+        _ => panic!("invalid directional key {key}"),
+    }
+}
+
+/// Returns the (at most 2) sensible key sequences to move between two keys
+///  Mixing horizontal and vertical moves is never better, so the only
+///  options are horizontal first or vertical first (if that avoids the gap).
+fn paths(from: Vector2<i32>, to: Vector2<i32>, gap: Vector2<i32>) -> Vec<Vec<u8>> {
+    let delta = to - from;
+    let horizontal = vec![if delta.x < 0 { b'<' } else { b'>' }; delta.x.unsigned_abs() as usize];
+    let vertical = vec![if delta.y < 0 { b'^' } else { b'v' }; delta.y.unsigned_abs() as usize];
+
+    let mut result = Vec::with_capacity(2);
+    if Vector2::new(to.x, from.y) != gap {
+        result.push([&horizontal[..], &vertical, b"A"].concat());
+    }
+    if Vector2::new(from.x, to.y) != gap && !horizontal.is_empty() && !vertical.is_empty() {
+        result.push([&vertical[..], &horizontal, b"A"].concat());
+    }
+    result
+}
+
+/// Returns the number of presses needed to type a sequence on a
+/// directional keypad which is `depth` robots away from the human
+fn directional_cost(cache: &mut Cache, seq: &[u8], depth: usize) -> u64 {
+    if depth == 0 {
+        return seq.len() as u64;
+    }
+
+    let mut prev = b'A';
+    let mut total = 0;
+    for &key in seq {
+        total += if let Some(&cost) = cache.get(&(prev, key, depth)) {
+            cost
+        } else {
+            let cost = paths(
+                directional_pos(prev),
+                directional_pos(key),
+                Vector2::new(0, 0),
+            )
+            .iter()
+            .map(|p| directional_cost(cache, p, depth - 1))
+            .min()
+            .unwrap();
+            cache.insert((prev, key, depth), cost);
+            cost
+        };
+        prev = key;
+    }
+    total
+}
+
+fn numeric_cost(cache: &mut Cache, code: &[u8], robots: usize) -> u64 {
+    let mut prev = b'A';
+    let mut total = 0;
+    for &key in code {
+        total += paths(numeric_pos(prev), numeric_pos(key), Vector2::new(0, 3))
+            .iter()
+            .map(|p| directional_cost(cache, p, robots))
+            .min()
+            .unwrap();
+        prev = key;
+    }
+    total
+}
+
+fn solve(input: &str, robots: usize) -> String {
+    let mut cache = HashMap::new();
+    input
+        .lines()
+        .map(|code| {
+            let value: u64 = code.trim_end_matches('A').parse().unwrap();
+            value * numeric_cost(&mut cache, code.as_bytes(), robots)
+        })
+        .sum::<u64>()
+        .to_string()
+}
+
+pub fn star1(input: &str) -> String {
+    solve(input, 2)
+}
+
+pub fn star2(input: &str) -> String {
+    solve(input, 25)
+}
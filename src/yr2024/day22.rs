@@ -0,0 +1,44 @@
+const PRUNE: u64 = 16_777_216;
+
+fn next_secret(mut secret: u64) -> u64 {
+    secret = ((secret * 64) ^ secret) % PRUNE;
+    secret = ((secret / 32) ^ secret) % PRUNE;
+    ((secret * 2048) ^ secret) % PRUNE
+}
+
+fn secrets(initial: u64) -> impl Iterator<Item = u64> {
+    std::iter::successors(Some(initial), |&s| Some(next_secret(s))).take(2001)
+}
+
+fn parse_buyers(input: &str) -> impl Iterator<Item = u64> + '_ {
+    input.lines().map(|line| line.parse().unwrap())
+}
+
+pub fn star1(input: &str) -> String {
+    parse_buyers(input)
+        .map(|b| secrets(b).last().unwrap())
+        .sum::<u64>()
+        .to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    // Each sequence of 4 changes is encoded as a base 19 number
+    const SEQUENCES: usize = 19 * 19 * 19 * 19;
+    let mut totals = vec![0u32; SEQUENCES];
+    let mut last_seen = vec![usize::MAX; SEQUENCES];
+
+    for (buyer, initial) in parse_buyers(input).enumerate() {
+        let prices: Vec<_> = secrets(initial).map(|s| (s % 10) as u32).collect();
+        let mut sequence = 0;
+        for (i, pair) in prices.windows(2).enumerate() {
+            let change = (pair[1] + 9 - pair[0]) as usize;
+            sequence = (sequence * 19 + change) % SEQUENCES;
+            if i >= 3 && last_seen[sequence] != buyer {
+                last_seen[sequence] = buyer;
+                totals[sequence] += pair[1];
+            }
+        }
+    }
+
+    totals.iter().max().unwrap().to_string()
+}
@@ -0,0 +1,79 @@
+use std::collections::{BTreeSet, HashMap};
+
+use itertools::Itertools;
+
+type Graph<'a> = HashMap<&'a str, BTreeSet<&'a str>>;
+
+fn parse_graph(input: &str) -> Graph<'_> {
+    let mut graph: Graph = HashMap::new();
+    for line in input.lines() {
+        let (a, b) = line.split_once('-').unwrap();
+        graph.entry(a).or_default().insert(b);
+        graph.entry(b).or_default().insert(a);
+    }
+    graph
+}
+
+pub fn star1(input: &str) -> String {
+    let graph = parse_graph(input);
+    let graph = &graph;
+    graph
+        .iter()
+        .flat_map(|(&a, links)| {
+            links
+                .iter()
+                .tuple_combinations()
+                .filter(move |&(&b, &c)| a < b && b < c && graph[b].contains(c))
+                .map(move |(&b, &c)| [a, b, c])
+        })
+        .filter(|tri| tri.iter().any(|n| n.starts_with('t')))
+        .count()
+        .to_string()
+}
+
+/// Finds the maximum clique using the Bron–Kerbosch algorithm with pivoting
+fn max_clique<'a>(
+    graph: &Graph<'a>,
+    current: &mut Vec<&'a str>,
+    mut candidates: BTreeSet<&'a str>,
+    mut excluded: BTreeSet<&'a str>,
+    best: &mut Vec<&'a str>,
+) {
+    if candidates.is_empty() && excluded.is_empty() {
+        if current.len() > best.len() {
+            best.clone_from(current);
+        }
+        return;
+    }
+
+    let pivot = candidates.iter().chain(&excluded).next().unwrap();
+    let to_try: Vec<_> = candidates.difference(&graph[pivot]).copied().collect();
+    for node in to_try {
+        let links = &graph[node];
+        current.push(node);
+        max_clique(
+            graph,
+            current,
+            candidates.intersection(links).copied().collect(),
+            excluded.intersection(links).copied().collect(),
+            best,
+        );
+        current.pop();
+        candidates.remove(node);
+        excluded.insert(node);
+    }
+}
+
+pub fn star2(input: &str) -> String {
+    let graph = parse_graph(input);
+    let mut best = Vec::new();
+    max_clique(
+        &graph,
+        &mut Vec::new(),
+        graph.keys().copied().collect(),
+        BTreeSet::new(),
+        &mut best,
+    );
+    best.sort_unstable();
+    best.join(",")
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Op {
+    And,
+    Or,
+    Xor,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Gate<'a> {
+    a: &'a str,
+    b: &'a str,
+    op: Op,
+    out: &'a str,
+}
+
+fn parse_input(input: &str) -> (HashMap<&str, bool>, Vec<Gate<'_>>) {
+    let (inputs, gates) = input.split_once("\n\n").unwrap();
+    (
+        inputs
+            .lines()
+            .map(|line| {
+                let (name, value) = line.split_once(": ").unwrap();
+                (name, value == "1")
+            })
+            .collect(),
+        gates
+            .lines()
+            .map(|line| {
+                let parts: Vec<_> = line.split(' ').collect();
+                Gate {
+                    a: parts[0],
+                    b: parts[2],
+                    op: match parts[1] {
+                        "AND" => Op::And,
+                        "OR" => Op::Or,
+                        "XOR" => Op::Xor,
+                        _ => panic!("invalid gate {line}"),
+                    },
+                    out: parts[4],
+                }
+            })
+            .collect(),
+    )
+}
+
+fn evaluate<'a>(
+    wires: &mut HashMap<&'a str, bool>,
+    gates: &HashMap<&'a str, Gate<'a>>,
+    wire: &'a str,
+) -> bool {
+    if let Some(&value) = wires.get(wire) {
+        return value;
+    }
+
+    let gate = gates[wire];
+    let a = evaluate(wires, gates, gate.a);
+    let b = evaluate(wires, gates, gate.b);
+    let value = match gate.op {
+        Op::And => a & b,
+        Op::Or => a | b,
+        Op::Xor => a ^ b,
+    };
+    wires.insert(wire, value);
+    value
+}
+
+pub fn star1(input: &str) -> String {
+    let (mut wires, gate_list) = parse_input(input);
+    let gates: HashMap<_, _> = gate_list.iter().map(|g| (g.out, *g)).collect();
+    let mut z_wires: Vec<_> = gates
+        .keys()
+        .filter(|w| w.starts_with('z'))
+        .copied()
+        .collect();
+    z_wires.sort_unstable();
+
+    z_wires
+        .into_iter()
+        .rev()
+        .fold(0u64, |acc, z| {
+            (acc << 1) | u64::from(evaluate(&mut wires, &gates, z))
+        })
+        .to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    let (_, gates) = parse_input(input);
+    let is_input = |w: &str| w.starts_with('x') || w.starts_with('y');
+    let is_first = |g: &Gate| g.a.ends_with("00") && g.b.ends_with("00");
+    let last_z = gates
+        .iter()
+        .map(|g| g.out)
+        .filter(|w| w.starts_with('z'))
+        .max()
+        .unwrap();
+    let feeds = |out: &str, op: Op| {
+        gates
+            .iter()
+            .any(|g| g.op == op && (g.a == out || g.b == out))
+    };
+
+    // The circuit should be a ripple carry adder, so find any gates which
+    // are not wired up the way a ripple carry adder would be
+    let mut wrong: Vec<_> = gates
+        .iter()
+        .filter(|g| {
+            if g.out.starts_with('z') && g.out != last_z {
+                g.op != Op::Xor || (is_input(g.a) && !is_first(g))
+            } else if g.out == last_z {
+                g.op != Op::Or
+            } else if g.op == Op::Xor && !is_input(g.a) {
+                true
+            } else if g.op == Op::Xor && !is_first(g) {
+                !feeds(g.out, Op::Xor)
+            } else if g.op == Op::And && !is_first(g) {
+                !feeds(g.out, Op::Or)
+            } else {
+                false
+            }
+        })
+        .map(|g| g.out)
+        .collect();
+
+    wrong.sort_unstable();
+    wrong.join(",")
+}
@@ -0,0 +1,19 @@
+pub fn star1(input: &str) -> String {
+    let (locks, keys): (Vec<_>, Vec<_>) = input
+        .split("\n\n")
+        .map(|block| {
+            let mask = block
+                .bytes()
+                .filter(|&b| b != b'\n')
+                .fold(0u64, |acc, b| (acc << 1) | u64::from(b == b'#'));
+            (block.starts_with('#'), mask)
+        })
+        .partition(|&(lock, _)| lock);
+
+    locks
+        .iter()
+        .flat_map(|l| keys.iter().map(move |k| (l.1, k.1)))
+        .filter(|(l, k)| l & k == 0)
+        .count()
+        .to_string()
+}
@@ -0,0 +1,28 @@
+use regex::Regex;
+
+fn solve(input: &str, conditionals: bool) -> String {
+    let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)|do\(\)|don't\(\)").unwrap();
+    let mut enabled = true;
+    let mut total = 0;
+
+    for cap in re.captures_iter(input) {
+        match &cap[0] {
+            "do()" => enabled = true,
+            "don't()" => enabled = !conditionals,
+            _ if enabled => {
+                total += cap[1].parse::<u32>().unwrap() * cap[2].parse::<u32>().unwrap();
+            }
+            _ => {}
+        }
+    }
+
+    total.to_string()
+}
+
+pub fn star1(input: &str) -> String {
+    solve(input, false)
+}
+
+pub fn star2(input: &str) -> String {
+    solve(input, true)
+}
@@ -0,0 +1,43 @@
+use itertools::Itertools;
+use nalgebra::DMatrix;
+
+fn parse_grid(input: &str) -> DMatrix<u8> {
+    let height = input.lines().count();
+    let width = input.lines().next().unwrap().len();
+    DMatrix::from_row_iterator(height, width, input.bytes().filter(u8::is_ascii_alphabetic))
+}
+
+fn get(grid: &DMatrix<u8>, y: usize, x: usize, dy: isize, dx: isize) -> Option<u8> {
+    let ny = y.checked_add_signed(dy)?;
+    let nx = x.checked_add_signed(dx)?;
+    grid.get((ny, nx)).copied()
+}
+
+pub fn star1(input: &str) -> String {
+    let grid = parse_grid(input);
+    (0..grid.nrows())
+        .cartesian_product(0..grid.ncols())
+        .cartesian_product((-1..=1).cartesian_product(-1..=1))
+        .filter(|&((y, x), (dy, dx))| {
+            b"XMAS"
+                .iter()
+                .zip(0..)
+                .all(|(&c, i)| get(&grid, y, x, dy * i, dx * i) == Some(c))
+        })
+        .count()
+        .to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    let grid = parse_grid(input);
+    let is_mas = |a, b| matches!((a, b), (Some(b'M'), Some(b'S')) | (Some(b'S'), Some(b'M')));
+    (0..grid.nrows())
+        .cartesian_product(0..grid.ncols())
+        .filter(|&(y, x)| {
+            grid[(y, x)] == b'A'
+                && is_mas(get(&grid, y, x, -1, -1), get(&grid, y, x, 1, 1))
+                && is_mas(get(&grid, y, x, -1, 1), get(&grid, y, x, 1, -1))
+        })
+        .count()
+        .to_string()
+}
@@ -0,0 +1,52 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+fn parse_input(input: &str) -> (HashSet<(u32, u32)>, Vec<Vec<u32>>) {
+    let (rules, updates) = input.split_once("\n\n").unwrap();
+    (
+        rules
+            .lines()
+            .map(|line| {
+                let (l, r) = line.split_once('|').unwrap();
+                (l.parse().unwrap(), r.parse().unwrap())
+            })
+            .collect(),
+        updates
+            .lines()
+            .map(|line| line.split(',').map(|n| n.parse().unwrap()).collect())
+            .collect(),
+    )
+}
+
+fn compare(rules: &HashSet<(u32, u32)>, a: u32, b: u32) -> Ordering {
+    if rules.contains(&(a, b)) {
+        Ordering::Less
+    } else if rules.contains(&(b, a)) {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+pub fn star1(input: &str) -> String {
+    let (rules, updates) = parse_input(input);
+    updates
+        .iter()
+        .filter(|u| u.is_sorted_by(|&a, &b| compare(&rules, a, b).is_le()))
+        .map(|u| u[u.len() / 2])
+        .sum::<u32>()
+        .to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    let (rules, updates) = parse_input(input);
+    updates
+        .into_iter()
+        .filter(|u| !u.is_sorted_by(|&a, &b| compare(&rules, a, b).is_le()))
+        .map(|mut u| {
+            u.sort_by(|&a, &b| compare(&rules, a, b));
+            u[u.len() / 2]
+        })
+        .sum::<u32>()
+        .to_string()
+}
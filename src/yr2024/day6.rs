@@ -0,0 +1,76 @@
+use crate::direction::Direction;
+use nalgebra::{DMatrix, Vector2};
+
+fn parse_grid(input: &str) -> (DMatrix<bool>, Vector2<i32>) {
+    let height = input.lines().count();
+    let width = input.lines().next().unwrap().len();
+    let cells: Vec<u8> = input.bytes().filter(u8::is_ascii_graphic).collect();
+    let start = cells.iter().position(|&b| b == b'^').unwrap();
+    (
+        DMatrix::from_row_iterator(height, width, cells.iter().map(|&b| b == b'#')),
+        Vector2::new((start % width) as i32, (start / width) as i32),
+    )
+}
+
+fn get(grid: &DMatrix<bool>, pos: Vector2<i32>) -> Option<bool> {
+    let y = usize::try_from(pos.y).ok()?;
+    let x = usize::try_from(pos.x).ok()?;
+    grid.get((y, x)).copied()
+}
+
+/// Walks the guard around the grid, calling `visit` for each new position
+///  Returns false if the guard got stuck in a loop
+fn walk(
+    grid: &DMatrix<bool>,
+    start: Vector2<i32>,
+    mut visit: impl FnMut(Vector2<i32>, Direction),
+) -> bool {
+    let mut seen = DMatrix::from_element(grid.nrows(), grid.ncols(), 0u8);
+    let mut pos = start;
+    let mut dir = Direction::North;
+
+    loop {
+        let mask = 1 << dir as u8;
+        let seen_cell = &mut seen[(pos.y as usize, pos.x as usize)];
+        if *seen_cell & mask != 0 {
+            return false;
+        }
+        if *seen_cell == 0 {
+            visit(pos, dir);
+        }
+        *seen_cell |= mask;
+
+        let next = pos + dir.to_vec_neg();
+        match get(grid, next) {
+            Some(true) => dir = dir.clockwise(),
+            Some(false) => pos = next,
+            None => return true,
+        }
+    }
+}
+
+pub fn star1(input: &str) -> String {
+    let (grid, start) = parse_grid(input);
+    let mut count = 0;
+    walk(&grid, start, |_, _| count += 1);
+    count.to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    let (mut grid, start) = parse_grid(input);
+    let mut path = Vec::new();
+    walk(&grid, start, |pos, _| path.push(pos));
+
+    // Only positions on the original path can change the guard's route
+    path.into_iter()
+        .skip(1)
+        .filter(|pos| {
+            let cell = (pos.y as usize, pos.x as usize);
+            grid[cell] = true;
+            let escaped = walk(&grid, start, |_, _| {});
+            grid[cell] = false;
+            !escaped
+        })
+        .count()
+        .to_string()
+}
@@ -0,0 +1,61 @@
+fn parse_equations(input: &str) -> impl Iterator<Item = (u64, Vec<u64>)> + '_ {
+    input.lines().map(|line| {
+        let (target, nums) = line.split_once(": ").unwrap();
+        (
+            target.parse().unwrap(),
+            nums.split(' ').map(|n| n.parse().unwrap()).collect(),
+        )
+    })
+}
+
+/// Works backwards from the target, undoing each operator where possible
+fn solvable(target: u64, nums: &[u64], concat: bool) -> bool {
+    match nums.split_last() {
+        None => false,
+        Some((&first, [])) => target == first,
+        Some((&last, rest)) => {
+            (target >= last && solvable(target - last, rest, concat))
+                || (if last == 0 {
+                    // Anything multiplied by zero is zero
+                    target == 0
+                } else {
+                    target.is_multiple_of(last) && solvable(target / last, rest, concat)
+                })
+                || (concat && {
+                    let shift = 10u64.pow(last.checked_ilog10().unwrap_or(0) + 1);
+                    target % shift == last && solvable(target / shift, rest, concat)
+                })
+        }
+    }
+}
+
+fn solve(input: &str, concat: bool) -> String {
+    parse_equations(input)
+        .filter(|(target, nums)| solvable(*target, nums, concat))
+        .map(|(target, _)| target)
+        .sum::<u64>()
+        .to_string()
+}
+
+pub fn star1(input: &str) -> String {
+    solve(input, false)
+}
+
+pub fn star2(input: &str) -> String {
+    solve(input, true)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn zero_operands() {
+        assert!(solvable(0, &[5, 0], false));
+        assert!(!solvable(1, &[5, 0], false));
+        assert!(solvable(5, &[5, 0], false));
+        assert!(solvable(50, &[5, 0], true));
+        assert_eq!(star1("0: 5 0"), "0");
+        assert_eq!(star1("5: 5 0\n3: 0 3"), "8");
+    }
+}
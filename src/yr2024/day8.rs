@@ -0,0 +1,55 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+use nalgebra::Vector2;
+
+type Antennas = HashMap<u8, Vec<Vector2<i32>>>;
+
+fn parse_map(input: &str) -> (Antennas, Vector2<i32>) {
+    let mut antennas: Antennas = HashMap::new();
+    for (y, line) in input.lines().enumerate() {
+        for (x, b) in line.bytes().enumerate() {
+            if b != b'.' {
+                antennas
+                    .entry(b)
+                    .or_default()
+                    .push(Vector2::new(x as i32, y as i32));
+            }
+        }
+    }
+
+    let height = input.lines().count() as i32;
+    let width = input.lines().next().unwrap().len() as i32;
+    (antennas, Vector2::new(width, height))
+}
+
+fn solve(input: &str, harmonics: bool) -> String {
+    let (antennas, size) = parse_map(input);
+    let in_bounds = |p: &Vector2<i32>| p.x >= 0 && p.y >= 0 && p.x < size.x && p.y < size.y;
+    let mut antinodes = HashSet::new();
+
+    for positions in antennas.values() {
+        for (&a, &b) in positions.iter().tuple_combinations() {
+            for (start, step) in [(a, a - b), (b, b - a)] {
+                if harmonics {
+                    antinodes.extend(
+                        std::iter::successors(Some(start), |&p| Some(p + step))
+                            .take_while(in_bounds),
+                    );
+                } else if in_bounds(&(start + step)) {
+                    antinodes.insert(start + step);
+                }
+            }
+        }
+    }
+
+    antinodes.len().to_string()
+}
+
+pub fn star1(input: &str) -> String {
+    solve(input, false)
+}
+
+pub fn star2(input: &str) -> String {
+    solve(input, true)
+}
@@ -0,0 +1,77 @@
+fn parse_map(input: &str) -> Vec<usize> {
+    input.bytes().map(|b| usize::from(b - b'0')).collect()
+}
+
+/// Sum of position * id for a file of the given length
+fn file_checksum(id: usize, start: usize, len: usize) -> usize {
+    id * (start..start + len).sum::<usize>()
+}
+
+pub fn star1(input: &str) -> String {
+    let map = parse_map(input);
+    let mut blocks: Vec<Option<usize>> = map
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &len)| std::iter::repeat_n((i % 2 == 0).then_some(i / 2), len))
+        .collect();
+
+    // Move blocks from the end into the free space
+    let mut front = 0;
+    let mut back = blocks.len();
+    loop {
+        while front < back && blocks[front].is_some() {
+            front += 1;
+        }
+        while back > front && blocks[back - 1].is_none() {
+            back -= 1;
+        }
+        if front + 1 >= back {
+            break;
+        }
+        blocks.swap(front, back - 1);
+    }
+
+    blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(i, b)| b.map(|id| i * id))
+        .sum::<usize>()
+        .to_string()
+}
+
+pub fn star2(input: &str) -> String {
+    let map = parse_map(input);
+
+    // Build lists of (start, len) for each file and gap
+    let mut files = Vec::new();
+    let mut gaps = Vec::new();
+    let mut pos = 0;
+    for (i, &len) in map.iter().enumerate() {
+        if i % 2 == 0 {
+            files.push((pos, len));
+        } else {
+            gaps.push((pos, len));
+        }
+        pos += len;
+    }
+
+    // Move whole files, highest id first, into the leftmost gap that fits
+    for file in files.iter_mut().rev() {
+        if let Some(gap) = gaps
+            .iter_mut()
+            .take_while(|g| g.0 < file.0)
+            .find(|g| g.1 >= file.1)
+        {
+            file.0 = gap.0;
+            gap.0 += file.1;
+            gap.1 -= file.1;
+        }
+    }
+
+    files
+        .into_iter()
+        .enumerate()
+        .map(|(id, (start, len))| file_checksum(id, start, len))
+        .sum::<usize>()
+        .to_string()
+}
@@ -1,7 +1,200 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use num::Integer;
+
+#[derive(Clone, Debug)]
+struct Machine {
+    lights: u32,
+    buttons: Vec<Vec<usize>>,
+    joltage: Vec<i64>,
+}
+
+fn parse_machines(input: &str) -> Vec<Machine> {
+    input
+        .lines()
+        .map(|line| {
+            let mut parts = line.split(' ');
+            let lights = parts
+                .next()
+                .unwrap()
+                .trim_matches(['[', ']'])
+                .bytes()
+                .rev()
+                .fold(0, |acc, b| (acc << 1) | u32::from(b == b'#'));
+            let mut lists: Vec<Vec<usize>> = parts
+                .map(|p| {
+                    p[1..p.len() - 1]
+                        .split(',')
+                        .map(|n| n.parse().unwrap())
+                        .collect()
+                })
+                .collect();
+            let joltage = lists.pop().unwrap().into_iter().map(|n| n as i64).collect();
+            Machine {
+                lights,
+                buttons: lists,
+                joltage,
+            }
+        })
+        .collect()
+}
+
+/// Finds the fewest button presses to set the lights (each button is
+/// pressed at most once since pressing it twice does nothing)
+fn min_light_presses(machine: &Machine) -> u32 {
+    let masks: Vec<u32> = machine
+        .buttons
+        .iter()
+        .map(|b| b.iter().fold(0, |acc, i| acc | (1 << i)))
+        .collect();
+    (0u32..1 << masks.len())
+        .filter(|subset| {
+            masks
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| subset & (1 << i) != 0)
+                .fold(0, |acc, (_, m)| acc ^ m)
+                == machine.lights
+        })
+        .map(u32::count_ones)
+        .min()
+        .expect("lights cannot be set")
+}
+
+pub fn star1(input: &str) -> String {
+    parse_machines(input)
+        .iter()
+        .map(min_light_presses)
+        .sum::<u32>()
+        .to_string()
+}
+
+/// System of linear equations in reduced row echelon form (using integers)
+///  Each row is the coefficients of each button followed by the target.
+struct ReducedSystem {
+    rows: Vec<Vec<i64>>,
+    pivots: Vec<usize>,
+    free: Vec<usize>,
+}
+
+impl ReducedSystem {
+    fn new(machine: &Machine) -> ReducedSystem {
+        let buttons = machine.buttons.len();
+        let mut rows: Vec<Vec<i64>> = machine
+            .joltage
+            .iter()
+            .enumerate()
+            .map(|(counter, &target)| {
+                let mut row: Vec<_> = machine
+                    .buttons
+                    .iter()
+                    .map(|b| i64::from(b.contains(&counter)))
+                    .collect();
+                row.push(target);
+                row
+            })
+            .collect();
+
+        let mut pivots = Vec::new();
+        let mut free = Vec::new();
+        for col in 0..buttons {
+            let rank = pivots.len();
+            let Some(pivot_row) = (rank..rows.len()).find(|&r| rows[r][col] != 0) else {
+                free.push(col);
+                continue;
+            };
+
+            rows.swap(rank, pivot_row);
+            if rows[rank][col] < 0 {
+                rows[rank].iter_mut().for_each(|v| *v = -*v);
+            }
+
+            // Eliminate this column from every other row
+            for r in 0..rows.len() {
+                let factor = rows[r][col];
+                if r != rank && factor != 0 {
+                    let pivot_row = rows[rank].clone();
+                    for (v, p) in rows[r].iter_mut().zip(&pivot_row) {
+                        *v = *v * pivot_row[col] - p * factor;
+                    }
+
+                    let divisor = rows[r].iter().fold(0, |acc, v| acc.gcd(v));
+                    if divisor > 1 {
+                        rows[r].iter_mut().for_each(|v| *v /= divisor);
+                    }
+                }
+            }
+
+            pivots.push(col);
+        }
+
+        assert!(
+            rows[pivots.len()..].iter().all(|row| row[buttons] == 0),
+            "joltage levels cannot be reached"
+        );
+        rows.truncate(pivots.len());
+        ReducedSystem { rows, pivots, free }
+    }
+
+    /// Calculates the total presses given values for the free variables
+    ///  Returns None if the pivot variables are not non-negative integers.
+    fn total_presses(&self, free_values: &[i64]) -> Option<i64> {
+        let mut total: i64 = free_values.iter().sum();
+        for (row, &pivot) in self.rows.iter().zip(&self.pivots) {
+            let rhs = row[row.len() - 1]
+                - self
+                    .free
+                    .iter()
+                    .zip(free_values)
+                    .map(|(&f, &v)| row[f] * v)
+                    .sum::<i64>();
+            let (value, rem) = rhs.div_rem(&row[pivot]);
+            if rem != 0 || value < 0 {
+                return None;
+            }
+            total += value;
+        }
+        Some(total)
+    }
+}
+
+/// Finds the fewest button presses to reach the joltage levels
+///  The system is solved exactly, then every possible value of the free
+///  variables is tried (bounded by the smallest target the button affects).
+fn min_joltage_presses(machine: &Machine) -> i64 {
+    let system = ReducedSystem::new(machine);
+    let bounds: Vec<i64> = system
+        .free
+        .iter()
+        .map(|&f| {
+            machine.buttons[f]
+                .iter()
+                .map(|&c| machine.joltage[c])
+                .min()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut values = vec![0; bounds.len()];
+    let mut best = None;
+    loop {
+        if let Some(total) = system.total_presses(&values) {
+            best = Some(best.map_or(total, |b: i64| b.min(total)));
+        }
+
+        // Advance to the next combination of free values
+        let Some(i) = (0..values.len()).find(|&i| values[i] < bounds[i]) else {
+            break;
+        };
+        values[i] += 1;
+        values[..i].fill(0);
+    }
+
+    best.expect("joltage levels cannot be reached")
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+pub fn star2(input: &str) -> String {
+    parse_machines(input)
+        .iter()
+        .map(min_joltage_presses)
+        .sum::<i64>()
+        .to_string()
 }
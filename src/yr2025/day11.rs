@@ -1,7 +1,63 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+type Graph<'a> = HashMap<&'a str, Vec<&'a str>>;
+
+fn parse_graph(input: &str) -> Graph<'_> {
+    input
+        .lines()
+        .map(|line| {
+            let (name, outputs) = line.split_once(": ").unwrap();
+            (name, outputs.split(' ').collect())
+        })
+        .collect()
+}
+
+/// Counts the paths between two devices (the graph must be acyclic)
+fn count_paths<'a>(
+    graph: &Graph<'a>,
+    cache: &mut HashMap<&'a str, u64>,
+    from: &'a str,
+    to: &'a str,
+) -> u64 {
+    if from == to {
+        return 1;
+    }
+    if let Some(&count) = cache.get(from) {
+        return count;
+    }
+
+    let count = graph
+        .get(from)
+        .into_iter()
+        .flatten()
+        .map(|next| count_paths(graph, cache, next, to))
+        .sum();
+    cache.insert(from, count);
+    count
+}
+
+/// Counts the paths from start to end which visit all the waypoints
+fn count_paths_via(graph: &Graph, start: &str, end: &str, waypoints: &[&str]) -> u64 {
+    waypoints
+        .iter()
+        .permutations(waypoints.len())
+        .map(|order| {
+            std::iter::once(&start)
+                .chain(order)
+                .chain(std::iter::once(&end))
+                .tuple_windows()
+                .map(|(from, to)| count_paths(graph, &mut HashMap::new(), from, to))
+                .product::<u64>()
+        })
+        .sum()
+}
+
+pub fn star1(input: &str) -> String {
+    count_paths_via(&parse_graph(input), "you", "out", &[]).to_string()
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+pub fn star2(input: &str) -> String {
+    count_paths_via(&parse_graph(input), "svr", "out", &["dac", "fft"]).to_string()
 }
@@ -1,7 +1,158 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use itertools::Itertools;
+
+/// Present shape stored as a list of (row, col) cells
+///  The cells are sorted so the first cell is the top-left one, and all
+///  other cells are relative to it.
+type Shape = Vec<(i32, i32)>;
+
+#[derive(Clone, Debug)]
+struct Region {
+    width: usize,
+    height: usize,
+    counts: Vec<usize>,
+}
+
+fn normalise(mut cells: Vec<(i32, i32)>) -> Shape {
+    cells.sort_unstable();
+    let (r0, c0) = cells[0];
+    cells.into_iter().map(|(r, c)| (r - r0, c - c0)).collect()
+}
+
+/// Returns all the distinct rotations and reflections of a shape
+fn orientations(shape: &Shape) -> Vec<Shape> {
+    let mut current = shape.clone();
+    let mut result = Vec::new();
+    for _ in 0..4 {
+        current = normalise(current.into_iter().map(|(r, c)| (c, -r)).collect());
+        result.push(current.clone());
+        result.push(normalise(current.iter().map(|&(r, c)| (r, -c)).collect()));
+    }
+    result.into_iter().unique().collect()
+}
+
+fn parse_input(input: &str) -> (Vec<Vec<Shape>>, Vec<Region>) {
+    let blocks: Vec<_> = input.split("\n\n").collect();
+    let (regions_str, shape_strs) = blocks.split_last().unwrap();
+
+    let shapes = shape_strs
+        .iter()
+        .map(|block| {
+            let cells = block
+                .lines()
+                .skip(1)
+                .enumerate()
+                .flat_map(|(r, line)| {
+                    line.bytes()
+                        .positions(|b| b == b'#')
+                        .map(move |c| (r as i32, c as i32))
+                })
+                .collect();
+            orientations(&normalise(cells))
+        })
+        .collect();
+
+    let regions = regions_str
+        .lines()
+        .map(|line| {
+            let (size, counts) = line.split_once(": ").unwrap();
+            let (width, height) = size.split_once('x').unwrap();
+            Region {
+                width: width.parse().unwrap(),
+                height: height.parse().unwrap(),
+                counts: counts.split(' ').map(|n| n.parse().unwrap()).collect(),
+            }
+        })
+        .collect();
+
+    (shapes, regions)
+}
+
+fn place(grid: &mut [Vec<bool>], shape: &Shape, row: i32, col: i32, value: bool) {
+    for &(r, c) in shape {
+        grid[(row + r) as usize][(col + c) as usize] = value;
+    }
+}
+
+/// Backtracking search which fills the grid one cell at a time
+///  The first empty cell is either left empty (if there is enough slack) or
+///  covered by the top-left cell of some remaining present.
+fn pack(
+    shapes: &[Vec<Shape>],
+    grid: &mut [Vec<bool>],
+    counts: &mut [usize],
+    cell: usize,
+    slack: usize,
+) -> bool {
+    if counts.iter().all(|&c| c == 0) {
+        return true;
+    }
+
+    let width = grid[0].len();
+    let Some(cell) = (cell..grid.len() * width).find(|&i| !grid[i / width][i % width]) else {
+        return false;
+    };
+    let (row, col) = ((cell / width) as i32, (cell % width) as i32);
+
+    for s in 0..shapes.len() {
+        if counts[s] == 0 {
+            continue;
+        }
+
+        for shape in &shapes[s] {
+            let fits = shape.iter().all(|&(r, c)| {
+                let (gr, gc) = (row + r, col + c);
+                gr >= 0
+                    && gc >= 0
+                    && (gc as usize) < width
+                    && grid.get(gr as usize).is_some_and(|line| !line[gc as usize])
+            });
+
+            if fits {
+                place(grid, shape, row, col, true);
+                counts[s] -= 1;
+                let found = pack(shapes, grid, counts, cell + 1, slack);
+                counts[s] += 1;
+                place(grid, shape, row, col, false);
+                if found {
+                    return true;
+                }
+            }
+        }
+    }
+
+    slack > 0 && pack(shapes, grid, counts, cell + 1, slack - 1)
+}
+
+fn fits(shapes: &[Vec<Shape>], region: &Region) -> bool {
+    let area = region.width * region.height;
+    let needed: usize = shapes
+        .iter()
+        .zip(&region.counts)
+        .map(|(s, &c)| s[0].len() * c)
+        .sum();
+    let presents: usize = region.counts.iter().sum();
+
+    if needed > area {
+        false
+    } else if (region.width / 3) * (region.height / 3) >= presents {
+        // Every present fits in its own 3x3 square (all the shapes are 3x3)
+        true
+    } else {
+        pack(
+            shapes,
+            &mut vec![vec![false; region.width]; region.height],
+            &mut region.counts.clone(),
+            0,
+            area - needed,
+        )
+    }
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+pub fn star1(input: &str) -> String {
+    let (shapes, regions) = parse_input(input);
+    regions
+        .iter()
+        .filter(|r| fits(&shapes, r))
+        .count()
+        .to_string()
 }
@@ -1,7 +1,124 @@
-pub fn star1(_input: &str) -> String {
-    todo!()
+use itertools::Itertools;
+use nalgebra::{DMatrix, Vector2};
+
+fn parse_tiles(input: &str) -> Vec<Vector2<i64>> {
+    input
+        .lines()
+        .map(|line| {
+            let (x, y) = line.split_once(',').unwrap();
+            Vector2::new(x.parse().unwrap(), y.parse().unwrap())
+        })
+        .collect()
+}
+
+fn area(a: Vector2<i64>, b: Vector2<i64>) -> i64 {
+    ((a.x - b.x).abs() + 1) * ((a.y - b.y).abs() + 1)
+}
+
+pub fn star1(input: &str) -> String {
+    parse_tiles(input)
+        .into_iter()
+        .tuple_combinations()
+        .map(|(a, b)| area(a, b))
+        .max()
+        .unwrap()
+        .to_string()
+}
+
+/// Compressed version of the tile floor
+///  Each distinct coordinate gets an odd index, and the gaps between them get
+///  the even indexes. Index 0 and the last index are always outside the loop.
+struct CompressedFloor {
+    xs: Vec<i64>,
+    ys: Vec<i64>,
+    /// Prefix sums of the number of cells outside the loop
+    outside: DMatrix<u32>,
+}
+
+impl CompressedFloor {
+    fn new(tiles: &[Vector2<i64>]) -> CompressedFloor {
+        let xs: Vec<_> = tiles
+            .iter()
+            .map(|t| t.x)
+            .sorted_unstable()
+            .dedup()
+            .collect();
+        let ys: Vec<_> = tiles
+            .iter()
+            .map(|t| t.y)
+            .sorted_unstable()
+            .dedup()
+            .collect();
+        let compress = |t: &Vector2<i64>| {
+            (
+                xs.binary_search(&t.x).unwrap() * 2 + 1,
+                ys.binary_search(&t.y).unwrap() * 2 + 1,
+            )
+        };
+
+        // Draw the loop
+        let (width, height) = (xs.len() * 2 + 1, ys.len() * 2 + 1);
+        let mut boundary = DMatrix::from_element(width, height, false);
+        for (a, b) in tiles.iter().circular_tuple_windows() {
+            let ((ax, ay), (bx, by)) = (compress(a), compress(b));
+            assert!(ax == bx || ay == by, "tiles are not in a straight line");
+            for x in ax.min(bx)..=ax.max(bx) {
+                for y in ay.min(by)..=ay.max(by) {
+                    boundary[(x, y)] = true;
+                }
+            }
+        }
+
+        // Flood fill the outside
+        let mut is_outside = DMatrix::from_element(width, height, false);
+        let mut open = vec![(0usize, 0usize)];
+        is_outside[(0, 0)] = true;
+        while let Some((x, y)) = open.pop() {
+            for (nx, ny) in [
+                (x + 1, y),
+                (x.wrapping_sub(1), y),
+                (x, y + 1),
+                (x, y.wrapping_sub(1)),
+            ] {
+                if nx < width && ny < height && !boundary[(nx, ny)] && !is_outside[(nx, ny)] {
+                    is_outside[(nx, ny)] = true;
+                    open.push((nx, ny));
+                }
+            }
+        }
+
+        let mut outside = DMatrix::zeros(width + 1, height + 1);
+        for x in 0..width {
+            for y in 0..height {
+                outside[(x + 1, y + 1)] =
+                    u32::from(is_outside[(x, y)]) + outside[(x, y + 1)] + outside[(x + 1, y)]
+                        - outside[(x, y)];
+            }
+        }
+
+        CompressedFloor { xs, ys, outside }
+    }
+
+    /// Returns true if the rectangle between the two red tiles is all inside the loop
+    fn is_inside(&self, a: Vector2<i64>, b: Vector2<i64>) -> bool {
+        let cx = |v| self.xs.binary_search(&v).unwrap() * 2 + 1;
+        let cy = |v| self.ys.binary_search(&v).unwrap() * 2 + 1;
+        let (x0, x1) = (cx(a.x.min(b.x)), cx(a.x.max(b.x)) + 1);
+        let (y0, y1) = (cy(a.y.min(b.y)), cy(a.y.max(b.y)) + 1);
+        self.outside[(x1, y1)] + self.outside[(x0, y0)]
+            == self.outside[(x0, y1)] + self.outside[(x1, y0)]
+    }
 }
 
-pub fn star2(_input: &str) -> String {
-    todo!()
+pub fn star2(input: &str) -> String {
+    let tiles = parse_tiles(input);
+    let floor = CompressedFloor::new(&tiles);
+    tiles
+        .iter()
+        .tuple_combinations()
+        .filter(|&(&a, &b)| floor.is_inside(a, b))
+        .map(|(&a, &b)| area(a, b))
+        .max()
+        .unwrap()
+        .to_string()
 }